@@ -1,4 +1,4 @@
-use rayimg::{materials::{Dielectric, Lambertian, Metal}, math::Vec3, shapes::Sphere, BVHNode, Camera, P3ImageWriter, Renderer, Scene, RGB};
+use rayimg::{materials::{Dielectric, Lambertian, Metal}, math::Vec3, shapes::{Plane, Sphere}, BVHNode, Camera, P3ImageWriter, Renderer, Scene, RGB};
 
 fn random_in_zero_to_one() -> f64 {
     Vec3::<f64>::random_in_unit_segment().x.abs()
@@ -8,7 +8,7 @@ fn main() {
     let mut scene = Scene::new();
 
     let ground_material = Lambertian::new(RGB::new(0.5, 0.5, 0.5));
-    scene.add_object(Sphere::new(Vec3::new(0.0, -1000.0, 0.0), 1000.0, ground_material));
+    scene.add_object(Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), ground_material));
     
     for a in -11..11 {
         for b in -11..11 {
@@ -0,0 +1,28 @@
+use crate::{math::{Ray, Vec3}, rgb::RGB};
+
+/// Describes color of rays that miss every object in the scene.
+pub trait Background {
+    /// Returns color seen along `ray`.
+    fn color(&self, ray: &Ray) -> RGB;
+
+    /// Returns true if background can be sampled directly with `random_direction`.
+    fn is_sampled(&self) -> bool {
+        false
+    }
+
+    /// Returns probability density (with respect to solid angle) of `random_direction` choosing `direction`.
+    fn pdf_value(&self, _direction: Vec3<f64>) -> f64 {
+        0.0
+    }
+
+    /// Returns random direction with probability proportional to brightness of the background.
+    fn random_direction(&self) -> Vec3<f64> {
+        Vec3::new(1.0, 0.0, 0.0)
+    }
+}
+
+impl<F> Background for F where F: Fn(&Ray) -> RGB {
+    fn color(&self, ray: &Ray) -> RGB {
+        self(ray)
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{background::Background, image_read::read_hdr, math::{Ray, Vec3}, random::random_in_range, rgb::RGB};
+
+use std::{f64::consts::PI, fs::File, io::{self, Read}, path::Path};
+
+/// Equirectangular image surrounding the scene, usually loaded from Radiance `.hdr` file.\
+/// Upper row of the image is seen in `+Y` direction. Bright regions of the map are sampled more often,
+/// so small light sources like sun are found with few samples.
+/// ```
+/// use rayimg::{backgrounds::EnvironmentMap, math::{Vec3, Ray}, Background, RGB};
+///
+/// let sky = EnvironmentMap::new(2, 2, vec![RGB(0.0, 0.0, 1.0), RGB(0.0, 0.0, 1.0), RGB(0.0, 1.0, 0.0), RGB(0.0, 1.0, 0.0)]).intensity(2.0);
+/// assert_eq!(sky.color(&Ray::new(Vec3::default(), Vec3::new(0.0, 1.0, 0.0))), RGB(0.0, 0.0, 2.0));
+/// assert_eq!(sky.color(&Ray::new(Vec3::default(), Vec3::new(0.0, -1.0, 0.0))), RGB(0.0, 2.0, 0.0));
+/// ```
+#[derive(Clone)]
+pub struct EnvironmentMap {
+    width: usize,
+    height: usize,
+    pixels: Vec<RGB>,
+    rotation: f64,
+    intensity: f64,
+    row_cdf: Vec<f64>,
+    column_cdfs: Vec<f64>
+}
+
+impl EnvironmentMap {
+    /// Creates new `EnvironmentMap` from `width * height` pixels starting from upper left corner.
+    pub fn new(width: usize, height: usize, pixels: Vec<RGB>) -> Self {
+        assert!(width > 0 && height > 0, "Environment map must not be empty");
+        assert_eq!(pixels.len(), width * height, "Pixel count does not match environment map bounds");
+
+        let (row_cdf, column_cdfs) = Self::build_distribution(width, height, &pixels);
+
+        Self {
+            width,
+            height,
+            pixels,
+            rotation: 0.0,
+            intensity: 1.0,
+            row_cdf,
+            column_cdfs
+        }
+    }
+
+    /// Loads `EnvironmentMap` from Radiance `.hdr` (RGBE) file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// Reads `EnvironmentMap` in Radiance `.hdr` (RGBE) format.
+    pub fn from_reader(reader: impl Read) -> io::Result<Self> {
+        let image = read_hdr(reader)?;
+        Ok(Self::new(image.width, image.height, image.pixels))
+    }
+
+    /// Sets rotation of the map around `Y` axis in **degrees**.
+    pub fn rotation(mut self, rotation: f64) -> Self {
+        self.rotation = rotation.to_radians();
+        self
+    }
+
+    /// Sets multiplier of map colors.
+    pub fn intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self
+    }
+
+    /// Returns map width and height.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn build_distribution(width: usize, height: usize, pixels: &[RGB]) -> (Vec<f64>, Vec<f64>) {
+        let mut row_cdf = vec![0.0; height + 1];
+        let mut column_cdfs = vec![0.0; height * (width + 1)];
+
+        for y in 0..height {
+            let sin_theta = (PI * (y as f64 + 0.5) / height as f64).sin();
+            let cdf = &mut column_cdfs[y * (width + 1)..(y + 1) * (width + 1)];
+            for x in 0..width {
+                cdf[x + 1] = cdf[x] + pixels[y * width + x].luminance().max(0.0) * sin_theta;
+            }
+
+            let row_sum = cdf[width];
+            for (x, value) in cdf.iter_mut().enumerate() {
+                *value = if row_sum > 0.0 { *value / row_sum } else { x as f64 / width as f64 };
+            }
+            row_cdf[y + 1] = row_cdf[y] + row_sum;
+        }
+
+        let total = row_cdf[height];
+        for (y, value) in row_cdf.iter_mut().enumerate() {
+            *value = if total > 0.0 { *value / total } else { y as f64 / height as f64 };
+        }
+
+        // A black map has no preferred direction, so fall back to sampling by pixel solid angle.
+        if total <= 0.0 {
+            let uniform = vec![RGB(1.0, 1.0, 1.0); pixels.len()];
+            return Self::build_distribution(width, height, &uniform);
+        }
+
+        (row_cdf, column_cdfs)
+    }
+
+    fn pixel_index(&self, direction: Vec3<f64>) -> (usize, usize) {
+        let direction = direction.normalize();
+        let theta = direction.y.clamp(-1.0, 1.0).acos();
+        let phi = direction.z.atan2(direction.x) - self.rotation;
+
+        let u = ((phi + PI) / (2.0 * PI)).rem_euclid(1.0);
+        let v = theta / PI;
+
+        (((u * self.width as f64) as usize).min(self.width - 1), ((v * self.height as f64) as usize).min(self.height - 1))
+    }
+
+    fn pixel_probability(&self, x: usize, y: usize) -> f64 {
+        let column_cdf = &self.column_cdfs[y * (self.width + 1)..];
+        (self.row_cdf[y + 1] - self.row_cdf[y]) * (column_cdf[x + 1] - column_cdf[x])
+    }
+}
+
+impl Background for EnvironmentMap {
+    fn color(&self, ray: &Ray) -> RGB {
+        let (x, y) = self.pixel_index(ray.direction());
+        self.pixels[y * self.width + x] * self.intensity
+    }
+
+    fn is_sampled(&self) -> bool {
+        true
+    }
+
+    fn pdf_value(&self, direction: Vec3<f64>) -> f64 {
+        let (x, y) = self.pixel_index(direction);
+        let sin_theta = (1.0 - direction.normalize().y.powi(2)).max(0.0).sqrt();
+        if sin_theta <= 0.0 {
+            return 0.0;
+        }
+
+        self.pixel_probability(x, y) * (self.width * self.height) as f64 / (2.0 * PI * PI * sin_theta)
+    }
+
+    fn random_direction(&self) -> Vec3<f64> {
+        let y = sample_cdf(&self.row_cdf, random_in_range(0.0..1.0));
+        let x = sample_cdf(&self.column_cdfs[y * (self.width + 1)..(y + 1) * (self.width + 1)], random_in_range(0.0..1.0));
+
+        let u = (x as f64 + random_in_range(0.0..1.0)) / self.width as f64;
+        let v = (y as f64 + random_in_range(0.0..1.0)) / self.height as f64;
+
+        let (phi, theta) = (2.0 * PI * u - PI + self.rotation, PI * v);
+        Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin())
+    }
+}
+
+fn sample_cdf(cdf: &[f64], value: f64) -> usize {
+    let last = cdf.len() - 2;
+    let mut index = cdf.partition_point(|&c| c <= value).saturating_sub(1).min(last);
+
+    // Skip zero-probability cells which share their bounds with a neighbour.
+    while index < last && cdf[index + 1] <= cdf[index] {
+        index += 1;
+    }
+
+    index
+}
@@ -0,0 +1,4 @@
+mod environment_map;
+mod sky;
+
+pub use {environment_map::EnvironmentMap, sky::Sky};
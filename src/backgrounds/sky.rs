@@ -0,0 +1,211 @@
+use crate::{background::Background, math::{Ray, Vec3, ONB}, random::random_in_range, rgb::RGB};
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Analytic daylight sky by Preetham, Shirley and Smits with the sun disk.\
+/// Sky color depends on sun direction and `turbidity` (haziness of the air, 2.0 is clear sky and 10.0 is hazy).
+/// Directions below the horizon see diffuse ground lit by sky and sun. The sun is sampled directly, so
+/// the sky works as background and as the main light of outdoor scenes.
+/// ```
+/// use rayimg::{backgrounds::Sky, math::{Vec3, Ray}, Background};
+///
+/// let sky = Sky::new(Vec3::new(0.0, 1.0, 1.0)).turbidity(3.0);
+/// let zenith = sky.color(&Ray::new(Vec3::default(), Vec3::new(0.0, 1.0, 0.0)));
+/// assert!(zenith.b() > zenith.r());
+/// assert!(sky.sun().luminance() > 1000.0 * zenith.luminance());
+/// ```
+#[derive(Debug, Clone)]
+pub struct Sky {
+    sun_direction: Vec3<f64>,
+    turbidity: f64,
+    ground_albedo: RGB,
+    intensity: f64,
+    zenith: [f64; 3],
+    perez: [[f64; 5]; 3],
+    sun: RGB,
+    ground: RGB
+}
+
+impl Sky {
+    const SUN_ANGULAR_RADIUS: f64 = 0.00465;
+    const SUN_LUMINANCE: f64 = 2.0e6;
+
+    /// Creates new `Sky` with sun in given direction, turbidity 3.0 and ground albedo 0.3.
+    pub fn new(sun_direction: Vec3<f64>) -> Self {
+        let mut sky = Self {
+            sun_direction: sun_direction.normalize(),
+            turbidity: 3.0,
+            ground_albedo: RGB(0.3, 0.3, 0.3),
+            intensity: 0.05,
+            zenith: [0.0; 3],
+            perez: [[0.0; 5]; 3],
+            sun: RGB::default(),
+            ground: RGB::default()
+        };
+
+        sky.precompute();
+        sky
+    }
+
+    /// Sets turbidity of the atmosphere, clamped to `1.7..=10.0`.
+    pub fn turbidity(mut self, turbidity: f64) -> Self {
+        self.turbidity = turbidity.clamp(1.7, 10.0);
+        self.precompute();
+        self
+    }
+
+    /// Sets albedo of the ground seen below the horizon.
+    pub fn ground_albedo(mut self, ground_albedo: RGB) -> Self {
+        self.ground_albedo = ground_albedo;
+        self.precompute();
+        self
+    }
+
+    /// Sets multiplier converting sky luminance in kcd/m² to scene radiance. Default is 0.05.
+    pub fn intensity(mut self, intensity: f64) -> Self {
+        self.intensity = intensity;
+        self.precompute();
+        self
+    }
+
+    /// Returns normalized direction to the sun.
+    pub fn sun_direction(&self) -> Vec3<f64> {
+        self.sun_direction
+    }
+
+    /// Returns radiance of the sun disk after passing through the atmosphere.
+    pub fn sun(&self) -> RGB {
+        self.sun
+    }
+
+    fn precompute(&mut self) {
+        let t = self.turbidity;
+        let theta_s = self.sun_direction.y.clamp(0.0, 1.0).acos();
+
+        self.perez = [
+            [-0.0193 * t - 0.2592, -0.0665 * t + 0.0008, -0.0004 * t + 0.2125, -0.0641 * t - 0.8989, -0.0033 * t + 0.0452],
+            [-0.0167 * t - 0.2608, -0.0950 * t + 0.0092, -0.0079 * t + 0.2102, -0.0441 * t - 1.6537, -0.0109 * t + 0.0529],
+            [0.1787 * t - 1.4630, -0.3554 * t + 0.4275, -0.0227 * t + 5.3251, 0.1206 * t - 2.5771, -0.0670 * t + 0.3703]
+        ];
+
+        let chi = (4.0 / 9.0 - t / 120.0) * (PI - 2.0 * theta_s);
+        let zenith_luminance = ((4.0453 * t - 4.9710) * chi.tan() - 0.2155 * t + 2.4192).max(0.0);
+
+        let (t2, theta2, theta3) = (t * t, theta_s * theta_s, theta_s * theta_s * theta_s);
+        let zenith_x = (0.00166 * theta3 - 0.00375 * theta2 + 0.00209 * theta_s) * t2
+            + (-0.02903 * theta3 + 0.06377 * theta2 - 0.03202 * theta_s + 0.00394) * t
+            + (0.11693 * theta3 - 0.21196 * theta2 + 0.06052 * theta_s + 0.25886);
+        let zenith_y = (0.00275 * theta3 - 0.00610 * theta2 + 0.00317 * theta_s) * t2
+            + (-0.04214 * theta3 + 0.08970 * theta2 - 0.04153 * theta_s + 0.00516) * t
+            + (0.15346 * theta3 - 0.26756 * theta2 + 0.06670 * theta_s + 0.26688);
+
+        self.zenith = [zenith_x, zenith_y, zenith_luminance];
+        self.sun = self.sun_transmittance(theta_s) * (Self::SUN_LUMINANCE * self.intensity);
+        self.ground = self.ground_albedo * (self.horizontal_irradiance() / PI);
+    }
+
+    /// Rayleigh and aerosol transmittance of sunlight for red, green and blue wavelengths.
+    fn sun_transmittance(&self, theta_s: f64) -> RGB {
+        if theta_s >= FRAC_PI_2 {
+            return RGB::default();
+        }
+
+        let optical_mass = 1.0 / (theta_s.cos() + 0.15 * (93.885 - theta_s.to_degrees()).powf(-1.253));
+        let beta = 0.04608 * self.turbidity - 0.04586;
+        let transmittance = |wavelength: f64| {
+            (-optical_mass * (0.008735 * wavelength.powf(-4.08) + beta * wavelength.powf(-1.3))).exp()
+        };
+
+        RGB(transmittance(0.65), transmittance(0.55), transmittance(0.45))
+    }
+
+    fn horizontal_irradiance(&self) -> RGB {
+        const THETA_STEPS: usize = 32;
+        const PHI_STEPS: usize = 64;
+
+        let mut irradiance = RGB::default();
+        for i in 0..THETA_STEPS {
+            let theta = (i as f64 + 0.5) / THETA_STEPS as f64 * FRAC_PI_2;
+            let weight = theta.cos() * theta.sin() * (FRAC_PI_2 / THETA_STEPS as f64) * (2.0 * PI / PHI_STEPS as f64);
+            for j in 0..PHI_STEPS {
+                let phi = (j as f64 + 0.5) / PHI_STEPS as f64 * 2.0 * PI;
+                let direction = Vec3::new(theta.sin() * phi.cos(), theta.cos(), theta.sin() * phi.sin());
+                irradiance += self.sky_radiance(direction) * weight;
+            }
+        }
+
+        let sun_solid_angle = 2.0 * PI * (1.0 - Self::SUN_ANGULAR_RADIUS.cos());
+        irradiance + self.sun * (sun_solid_angle * self.sun_direction.y.max(0.0))
+    }
+
+    fn perez(coefficients: &[f64; 5], theta: f64, gamma: f64) -> f64 {
+        let [a, b, c, d, e] = *coefficients;
+        (1.0 + a * (b / theta.cos().max(1e-3)).exp()) * (1.0 + c * (d * gamma).exp() + e * gamma.cos().powi(2))
+    }
+
+    fn sky_radiance(&self, direction: Vec3<f64>) -> RGB {
+        let theta = direction.y.clamp(0.0, 1.0).acos();
+        let gamma = direction.dot(&self.sun_direction).clamp(-1.0, 1.0).acos();
+        let theta_s = self.sun_direction.y.clamp(0.0, 1.0).acos();
+
+        let mut xy_luminance = [0.0; 3];
+        for (index, value) in xy_luminance.iter_mut().enumerate() {
+            let coefficients = &self.perez[index];
+            *value = self.zenith[index] * Self::perez(coefficients, theta, gamma) / Self::perez(coefficients, 0.0, theta_s);
+        }
+
+        let [x, y, luminance] = xy_luminance;
+        if y <= 0.0 {
+            return RGB::default();
+        }
+
+        let color = RGB::from_xyz(x / y * luminance, luminance, (1.0 - x - y) / y * luminance);
+        RGB(color.0.max(0.0), color.1.max(0.0), color.2.max(0.0)) * self.intensity
+    }
+
+    fn sun_visible(&self) -> bool {
+        self.sun_direction.y > 0.0
+    }
+
+    fn sun_cone_pdf() -> f64 {
+        1.0 / (2.0 * PI * (1.0 - Self::SUN_ANGULAR_RADIUS.cos()))
+    }
+}
+
+impl Background for Sky {
+    fn color(&self, ray: &Ray) -> RGB {
+        let direction = ray.direction().normalize();
+        if direction.y < 0.0 {
+            return self.ground;
+        }
+
+        let in_sun = self.sun_visible() && direction.dot(&self.sun_direction) >= Self::SUN_ANGULAR_RADIUS.cos();
+        if in_sun {
+            self.sky_radiance(direction) + self.sun
+        } else {
+            self.sky_radiance(direction)
+        }
+    }
+
+    fn is_sampled(&self) -> bool {
+        true
+    }
+
+    fn pdf_value(&self, direction: Vec3<f64>) -> f64 {
+        let uniform_pdf = 0.25 / PI;
+        if !self.sun_visible() {
+            return uniform_pdf;
+        }
+
+        let in_sun = direction.normalize().dot(&self.sun_direction) >= Self::SUN_ANGULAR_RADIUS.cos();
+        0.5 * uniform_pdf + if in_sun { 0.5 * Self::sun_cone_pdf() } else { 0.0 }
+    }
+
+    fn random_direction(&self) -> Vec3<f64> {
+        if !self.sun_visible() || random_in_range(0.0..1.0) < 0.5 {
+            return Vec3::random_unit_vector();
+        }
+
+        ONB::new(self.sun_direction).local(Vec3::random_in_cone(Self::SUN_ANGULAR_RADIUS.cos()))
+    }
+}
@@ -37,10 +37,44 @@ impl AABB {
 
         aabb
     }
-}
 
-impl Hit for AABB {
-    fn hit(&self, ray: &Ray, mut t_min: f64, mut t_max: f64) -> Option<HitRecord> {
+    /// Returns box covering all space, bounding of unbounded objects (e.g. `Plane`).
+    /// ```
+    /// # use rayimg::AABB;
+    /// assert!(!AABB::universe().is_bounded());
+    /// assert!(AABB::new().is_bounded());
+    /// ```
+    pub fn universe() -> Self {
+        Self {
+            axes: [Interval::limits(f64::NEG_INFINITY, f64::INFINITY); 3]
+        }
+    }
+
+    /// Returns false if the box is infinite along some axis.
+    pub fn is_bounded(&self) -> bool {
+        self.axes.iter().all(|axis| axis.min.is_finite() && axis.max.is_finite())
+    }
+
+    /// Expands axes shorter than `delta` so that flat objects (e.g. triangles) have non-empty bounding.
+    pub fn pad(&self, delta: f64) -> Self {
+        let mut aabb = *self;
+
+        for axis in &mut aabb.axes {
+            if axis.len() < delta {
+                *axis = axis.expand(delta);
+            }
+        }
+
+        aabb
+    }
+
+    /// Returns part `(t_min, t_max)` of the ray inside the box, or None if ray misses it.
+    /// ```
+    /// # use rayimg::{math::{Vec3, Ray}, AABB};
+    /// let aabb = AABB::from_two_points(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    /// assert_eq!(aabb.clip(&Ray::new(Vec3::new(0.0, 0.0, 3.0), Vec3::new(0.0, 0.0, -1.0)), 0.0, f64::MAX), Some((2.0, 4.0)));
+    /// ```
+    pub fn clip(&self, ray: &Ray, mut t_min: f64, mut t_max: f64) -> Option<(f64, f64)> {
         for index in 0..3 {
             let one_over_direction = 1.0 / ray.direction()[index];
             let origin = ray.origin()[index];
@@ -65,7 +99,18 @@ impl Hit for AABB {
             }
         }
 
-        Some(HitRecord::new(0.0, Vec3::default()))
+        Some((t_min, t_max))
+    }
+
+    /// Returns center point of the box.
+    pub fn center(&self) -> Vec3<f64> {
+        Vec3::new(self.axes[0].min + self.axes[0].max, self.axes[1].min + self.axes[1].max, self.axes[2].min + self.axes[2].max) * 0.5
+    }
+}
+
+impl Hit for AABB {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        self.clip(ray, t_min, t_max).map(|_| HitRecord::new(0.0, Vec3::default()))
     }
 
     fn bounding(&self) -> AABB {
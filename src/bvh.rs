@@ -2,15 +2,39 @@ use std::{cmp::Ordering, sync::Arc};
 
 use crate::{math::Ray, random::random_in_range, Hit, HitRecord, Scene, AABB};
 
+/// Bounding volume hierarchy over objects of `Scene`.\
+/// Unbounded objects (e.g. `Plane`) are kept out of the hierarchy and tested separately.
+/// ```
+/// use rayimg::{BVHNode, Scene, shapes::{Plane, Sphere}, materials::Lambertian, math::{Vec3, Ray}, RGB, Hit};
+///
+/// let mut scene = Scene::new();
+/// scene.add_object(Sphere::new(Vec3::new(0.0, 1.0, 0.0), 1.0, Lambertian::new(RGB::default())));
+/// scene.add_object(Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB::default())));
+/// let bvh = BVHNode::from_scene(scene);
+///
+/// assert!(!bvh.bounding().is_bounded());
+/// assert_eq!(bvh.hit(&Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap().t(), 3.0);
+/// assert_eq!(bvh.hit(&Ray::new(Vec3::new(50.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap().t(), 5.0);
+/// ```
 pub struct BVHNode<'a> {
     left: Arc<dyn Hit + 'a + Send + Sync>,
     right: Arc<dyn Hit + 'a + Send + Sync>,
+    unbounded: Vec<Arc<dyn Hit + 'a + Send + Sync>>,
     aabb: AABB
 }
 
 impl<'a> BVHNode<'a> {
     pub fn from_scene(scene: Scene<'a>) -> Self {
-        BVHNode::from_objects(&mut scene.objects())
+        let (mut bounded, unbounded): (Vec<_>, Vec<_>) = scene.objects().into_iter().partition(|object| object.bounding().is_bounded());
+        if bounded.is_empty() {
+            // Empty scene stands in for the hierarchy, it's never hit.
+            bounded.push(Arc::new(Scene::new()));
+        }
+
+        Self {
+            unbounded,
+            ..BVHNode::from_objects(&mut bounded)
+        }
     }
 
     fn from_objects(objects: &mut [Arc<dyn Hit + 'a + Send + Sync>]) -> Self {
@@ -36,6 +60,7 @@ impl<'a> BVHNode<'a> {
         Self {
             left: left.clone(),
             right: right.clone(),
+            unbounded: Vec::new(),
             aabb: AABB::unite(left.bounding(), right.bounding())
         }
     }
@@ -48,12 +73,19 @@ impl<'a> BVHNode<'a> {
 }
 
 impl<'a> Hit for BVHNode<'a> {
-    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitRecord<'_>> {
+        let mut hit_record_option = None;
+        for object in &self.unbounded {
+            if let Some(temp_hit_record) = object.hit(ray, t_min, t_max) {
+                hit_record_option = Some(temp_hit_record);
+                t_max = temp_hit_record.t();
+            }
+        }
+
         if self.aabb.hit(ray, t_min, t_max).is_none() {
-            return None;
+            return hit_record_option;
         }
 
-        let mut hit_record_option = None;
         if let Some(temp_hit_record) = self.left.hit(ray, t_min, t_max) {
             hit_record_option = Some(temp_hit_record);
             t_max = temp_hit_record.t();
@@ -67,6 +99,6 @@ impl<'a> Hit for BVHNode<'a> {
     }
 
     fn bounding(&self) -> AABB {
-        self.aabb
+        if self.unbounded.is_empty() { self.aabb } else { AABB::universe() }
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,39 @@
+use crate::{distances::{Repeat, Round, SmoothUnion, Translate, Twist}, math::Vec3};
+
+/// Signed distance from a point to the surface of an object, negative inside it, e.g. for `shapes::Sdf`.\
+/// Distance functions can be combined into trees by the methods below.
+pub trait Distance {
+    /// Returns signed distance from `point`. It may underestimate the distance, but must not overestimate it.
+    fn distance(&self, point: Vec3<f64>) -> f64;
+
+    /// Rounds the object, growing its surface outwards by `radius`.
+    fn round(self, radius: f64) -> Round<Self> where Self: Sized {
+        Round::new(self, radius)
+    }
+
+    /// Blends the object with `other`, filling corners where they meet within `smoothness`.
+    fn smooth_union<D: Distance>(self, other: D, smoothness: f64) -> SmoothUnion<Self, D> where Self: Sized {
+        SmoothUnion::new(self, other, smoothness)
+    }
+
+    /// Twists the object around `y` axis by `rate` degrees per unit of height.
+    fn twist(self, rate: f64) -> Twist<Self> where Self: Sized {
+        Twist::new(self, rate)
+    }
+
+    /// Repeats the object infinitely in cells of size `period` centered at the origin. Zero component of period disables repetition along the axis.
+    fn repeat(self, period: Vec3<f64>) -> Repeat<Self> where Self: Sized {
+        Repeat::new(self, period)
+    }
+
+    /// Moves the object by `offset`.
+    fn translate(self, offset: Vec3<f64>) -> Translate<Self> where Self: Sized {
+        Translate::new(self, offset)
+    }
+}
+
+impl<F> Distance for F where F: Fn(Vec3<f64>) -> f64 {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        self(point)
+    }
+}
@@ -0,0 +1,4 @@
+mod primitives;
+mod operations;
+
+pub use {primitives::{Sphere, Cuboid}, operations::{Round, SmoothUnion, Twist, Repeat, Translate}};
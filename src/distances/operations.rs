@@ -0,0 +1,140 @@
+use crate::{math::Vec3, Distance};
+
+/// Object grown outwards by `radius`, which rounds its edges. See `Distance::round`.
+#[derive(Debug, Clone, Copy)]
+pub struct Round<D> {
+    distance: D,
+    radius: f64
+}
+
+impl<D: Distance> Round<D> {
+    /// Creates new `Round`.
+    pub fn new(distance: D, radius: f64) -> Self {
+        Self {
+            distance,
+            radius
+        }
+    }
+}
+
+impl<D: Distance> Distance for Round<D> {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        self.distance.distance(point) - self.radius
+    }
+}
+
+/// Union of two objects blended by polynomial smooth minimum. See `Distance::smooth_union`.
+/// ```
+/// # use rayimg::{distances::Sphere, math::Vec3, Distance};
+/// let blob = Sphere::new(1.0).translate(Vec3::new(-1.0, 0.0, 0.0)).smooth_union(Sphere::new(1.0).translate(Vec3::new(1.0, 0.0, 0.0)), 0.5);
+/// // Smooth union is fuller than both objects where they meet.
+/// assert!(blob.distance(Vec3::new(0.0, 0.0, 0.0)) < -0.1);
+/// assert_eq!(blob.distance(Vec3::new(-3.0, 0.0, 0.0)), 1.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct SmoothUnion<A, B> {
+    first: A,
+    second: B,
+    smoothness: f64
+}
+
+impl<A: Distance, B: Distance> SmoothUnion<A, B> {
+    /// Creates new `SmoothUnion`.
+    pub fn new(first: A, second: B, smoothness: f64) -> Self {
+        Self {
+            first,
+            second,
+            smoothness
+        }
+    }
+}
+
+impl<A: Distance, B: Distance> Distance for SmoothUnion<A, B> {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        let (a, b) = (self.first.distance(point), self.second.distance(point));
+        if self.smoothness <= 0.0 {
+            return a.min(b);
+        }
+
+        let h = (0.5 + 0.5 * (b - a) / self.smoothness).clamp(0.0, 1.0);
+        b + (a - b) * h - self.smoothness * h * (1.0 - h)
+    }
+}
+
+/// Object twisted around `y` axis. See `Distance::twist`.\
+/// Twisting stretches space, so `shapes::Sdf` marching it needs shorter steps.
+#[derive(Debug, Clone, Copy)]
+pub struct Twist<D> {
+    distance: D,
+    rate: f64
+}
+
+impl<D: Distance> Twist<D> {
+    /// Creates new `Twist` by `rate` degrees per unit of height.
+    pub fn new(distance: D, rate: f64) -> Self {
+        Self {
+            distance,
+            rate: rate.to_radians()
+        }
+    }
+}
+
+impl<D: Distance> Distance for Twist<D> {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        let (sin, cos) = (self.rate * point.y).sin_cos();
+        self.distance.distance(Vec3::new(cos * point.x - sin * point.z, point.y, sin * point.x + cos * point.z))
+    }
+}
+
+/// Object repeated infinitely on a grid. See `Distance::repeat`.
+/// ```
+/// # use rayimg::{distances::Sphere, math::Vec3, Distance};
+/// let spheres = Sphere::new(0.5).repeat(Vec3::new(2.0, 0.0, 2.0));
+/// assert_eq!(spheres.distance(Vec3::new(10.0, 0.0, -4.0)), -0.5);
+/// assert_eq!(spheres.distance(Vec3::new(10.0, 3.0, -4.0)), 2.5);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat<D> {
+    distance: D,
+    period: Vec3<f64>
+}
+
+impl<D: Distance> Repeat<D> {
+    /// Creates new `Repeat`.
+    pub fn new(distance: D, period: Vec3<f64>) -> Self {
+        Self {
+            distance,
+            period
+        }
+    }
+}
+
+impl<D: Distance> Distance for Repeat<D> {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        let wrap = |x: f64, period: f64| if period > 0.0 { x - period * (x / period).round() } else { x };
+        self.distance.distance(Vec3::new(wrap(point.x, self.period.x), wrap(point.y, self.period.y), wrap(point.z, self.period.z)))
+    }
+}
+
+/// Object moved by `offset`. See `Distance::translate`.
+#[derive(Debug, Clone, Copy)]
+pub struct Translate<D> {
+    distance: D,
+    offset: Vec3<f64>
+}
+
+impl<D: Distance> Translate<D> {
+    /// Creates new `Translate`.
+    pub fn new(distance: D, offset: Vec3<f64>) -> Self {
+        Self {
+            distance,
+            offset
+        }
+    }
+}
+
+impl<D: Distance> Distance for Translate<D> {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        self.distance.distance(point - self.offset)
+    }
+}
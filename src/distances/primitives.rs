@@ -0,0 +1,58 @@
+use crate::{math::Vec3, Distance};
+
+/// Distance to sphere of `radius` around the origin.
+/// ```
+/// # use rayimg::{distances::Sphere, math::Vec3, Distance};
+/// let sphere = Sphere::new(1.0);
+/// assert_eq!(sphere.distance(Vec3::new(0.0, 3.0, 0.0)), 2.0);
+/// assert_eq!(sphere.distance(Vec3::new(0.0, 0.0, 0.0)), -1.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Sphere {
+    radius: f64
+}
+
+impl Sphere {
+    /// Creates new `Sphere`.
+    pub fn new(radius: f64) -> Self {
+        Self {
+            radius
+        }
+    }
+}
+
+impl Distance for Sphere {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        point.len() - self.radius
+    }
+}
+
+/// Distance to axis-aligned box around the origin, extending by `half_size` in each direction.
+/// ```
+/// # use rayimg::{distances::Cuboid, math::Vec3, Distance};
+/// let cuboid = Cuboid::new(Vec3::new(1.0, 2.0, 1.0));
+/// assert_eq!(cuboid.distance(Vec3::new(0.0, 3.0, 0.0)), 1.0);
+/// assert_eq!(cuboid.distance(Vec3::new(4.0, 6.0, 0.0)), 5.0);
+/// assert_eq!(cuboid.distance(Vec3::new(0.0, 0.0, 0.0)), -1.0);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct Cuboid {
+    half_size: Vec3<f64>
+}
+
+impl Cuboid {
+    /// Creates new `Cuboid`.
+    pub fn new(half_size: Vec3<f64>) -> Self {
+        Self {
+            half_size
+        }
+    }
+}
+
+impl Distance for Cuboid {
+    fn distance(&self, point: Vec3<f64>) -> f64 {
+        let q = Vec3::new(point.x.abs() - self.half_size.x, point.y.abs() - self.half_size.y, point.z.abs() - self.half_size.z);
+        let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).len();
+        outside + q.x.max(q.y).max(q.z).min(0.0)
+    }
+}
@@ -1,10 +1,51 @@
-use crate::{math::Ray, AABB};
+use crate::{math::{Ray, Vec3}, AABB};
 use super::hit_record::HitRecord;
 
+use std::sync::Arc;
+
 /// An object that ray can `Hit`.
 pub trait Hit {
     /// Returns `HitRecord` if ray hits object that implements `Hit` trait or None if ray does not intersects with it.
-    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord>;
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>>;
 
     fn bounding(&self) -> AABB;
-}
\ No newline at end of file
+
+    /// Returns estimated power of light emitted by the object, so that brighter lights are sampled more often.
+    fn power(&self) -> f64 {
+        0.0
+    }
+
+    /// Returns probability density (with respect to solid angle) of `random_direction` choosing `direction` from `origin`.\
+    /// Objects that can't be sampled as lights return 0.0.
+    fn pdf_value(&self, _origin: Vec3<f64>, _direction: Vec3<f64>) -> f64 {
+        0.0
+    }
+
+    /// Returns random direction from `origin` towards the object.
+    fn random_direction(&self, _origin: Vec3<f64>) -> Vec3<f64> {
+        Vec3::new(1.0, 0.0, 0.0)
+    }
+}
+
+/// Shared object, so the same object (e.g. mesh) can be placed in the scene several times by `Transformed`.
+impl<H: Hit + ?Sized> Hit for Arc<H> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        (**self).hit(ray, t_min, t_max)
+    }
+
+    fn bounding(&self) -> AABB {
+        (**self).bounding()
+    }
+
+    fn power(&self) -> f64 {
+        (**self).power()
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        (**self).pdf_value(origin, direction)
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        (**self).random_direction(origin)
+    }
+}
@@ -1,16 +1,21 @@
-use crate::{math::{Ray, Vec3}, RGB};
+use crate::{math::{Ray, Vec3}, scatter::Scatter, RGB};
+
+use std::fmt;
 
 /// Record of ray-object intersection.
-#[derive(Debug, Clone, Copy)]
-pub struct HitRecord {
+#[derive(Clone, Copy)]
+pub struct HitRecord<'a> {
     t: f64,
     point: Vec3<f64>,
     normal: Vec3<f64>,
+    geometric_normal: Vec3<f64>,
     front_face: bool,
-    scatter: Option<(Ray, RGB)>
+    uv: (f64, f64),
+    tangents: (Vec3<f64>, Vec3<f64>),
+    material: Option<&'a dyn Scatter>
 }
 
-impl HitRecord {
+impl<'a> HitRecord<'a> {
     /// Creates new `HitRecord`.
     /// ```
     /// # use rayimg::{HitRecord, math::Vec3, materials::Lambertian, RGB};
@@ -22,8 +27,11 @@ impl HitRecord {
             t,
             point,
             normal: Vec3::default(),
+            geometric_normal: Vec3::default(),
             front_face: bool::default(),
-            scatter: None
+            uv: (0.0, 0.0),
+            tangents: (Vec3::default(), Vec3::default()),
+            material: None
         }
     }
 
@@ -47,22 +55,75 @@ impl HitRecord {
         self.point.clone()
     }
 
-    /// Sets face normal and determines whether normal points inwards or outwards
+    /// Sets face normal and determines whether normal points inwards or outwards.\
+    /// Both geometric and shading normals are set to the normal facing against the ray.
     pub fn set_face_normal(&mut self, ray: &Ray, normal: Vec3<f64>) {
         self.front_face = ray.direction().dot(&normal) < f64::default();
+        self.geometric_normal = if self.front_face { normal } else { -normal };
+        self.normal = self.geometric_normal;
+    }
+
+    /// Sets outward shading normal (e.g. interpolated or perturbed) of **unit length**. It is flipped to the side of geometric normal,
+    /// so must be called after `set_face_normal`.
+    /// ```
+    /// # use rayimg::{HitRecord, math::{Vec3, Ray}};
+    /// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, -1.0));
+    /// hit_record.set_face_normal(&Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0)), Vec3::new(0.0, 0.0, -1.0));
+    /// hit_record.set_shading_normal(Vec3::new(0.0, 0.6, -0.8));
+    /// assert_eq!(hit_record.normal(), Vec3::new(0.0, -0.6, 0.8));
+    /// assert_eq!(hit_record.geometric_normal(), Vec3::new(0.0, 0.0, 1.0));
+    /// ```
+    pub fn set_shading_normal(&mut self, normal: Vec3<f64>) {
         self.normal = if self.front_face { normal } else { -normal };
     }
 
-    pub fn set_scatter(&mut self, scatter: (Ray, RGB)) {
-        self.scatter = Some(scatter);
+    /// Sets partial derivatives of hit point with respect to surface coordinates `u` and `v`.
+    pub fn set_tangents(&mut self, dpdu: Vec3<f64>, dpdv: Vec3<f64>) {
+        self.tangents = (dpdu, dpdv);
+    }
+
+    /// Returns partial derivatives of hit point with respect to surface coordinates `u` and `v`.
+    pub fn tangents(&self) -> (Vec3<f64>, Vec3<f64>) {
+        self.tangents
+    }
+
+    /// Sets surface coordinates of hit point, both in interval `0.0..=1.0`.
+    pub fn set_uv(&mut self, u: f64, v: f64) {
+        self.uv = (u, v);
+    }
+
+    /// Returns surface coordinates of hit point.
+    /// ```
+    /// # use rayimg::{HitRecord, math::Vec3};
+    /// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, -1.0));
+    /// hit_record.set_uv(0.25, 0.5);
+    /// assert_eq!(hit_record.uv(), (0.25, 0.5));
+    /// ```
+    pub fn uv(&self) -> (f64, f64) {
+        self.uv
+    }
+
+    /// Sets material of hit surface.
+    pub fn set_material(&mut self, material: &'a dyn Scatter) {
+        self.material = Some(material);
+    }
+
+    /// Returns material of hit surface.
+    pub fn material(&self) -> Option<&'a dyn Scatter> {
+        self.material
     }
 
     /// Get scattering result
-    pub fn scatter(&self) -> Option<(Ray, RGB)> {
-        self.scatter.clone()
+    pub fn scatter(&self, ray: &Ray) -> Option<(Ray, RGB)> {
+        self.material.and_then(|material| material.scatter(ray, self))
+    }
+
+    /// Returns light emitted by hit surface.
+    pub fn emitted(&self, ray: &Ray) -> RGB {
+        self.material.map_or(RGB::default(), |material| material.emit(ray, self))
     }
 
-    /// Returns normal of hit surface
+    /// Returns shading normal of hit surface facing against the ray. Materials scatter light around this normal.
     /// ```
     /// # use rayimg::{HitRecord, math::{Vec3, Ray}, materials::Lambertian, RGB};
     /// let mut hit_record = HitRecord::new(5.7, Vec3::new(-4.0, 2.0, 3.0));
@@ -73,8 +134,27 @@ impl HitRecord {
         self.normal.clone()
     }
 
+    /// Returns geometric normal of hit surface facing against the ray.
+    pub fn geometric_normal(&self) -> Vec3<f64> {
+        self.geometric_normal
+    }
+
     /// Returns true if normal points outwards.
     pub fn front_face(&self) -> bool {
         self.front_face
     }
 }
+
+impl<'a> fmt::Debug for HitRecord<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HitRecord")
+            .field("t", &self.t)
+            .field("point", &self.point)
+            .field("normal", &self.normal)
+            .field("geometric_normal", &self.geometric_normal)
+            .field("front_face", &self.front_face)
+            .field("uv", &self.uv)
+            .field("tangents", &self.tangents)
+            .finish_non_exhaustive()
+    }
+}
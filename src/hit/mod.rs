@@ -1,4 +1,6 @@
 mod hit;
 mod hit_record;
+mod solid;
 
-pub use {hit::Hit, hit_record::HitRecord};
\ No newline at end of file
+pub use {hit::Hit, hit_record::HitRecord, solid::Solid};
+pub(crate) use solid::successive_hits;
@@ -0,0 +1,34 @@
+use crate::math::Ray;
+use super::{hit::Hit, hit_record::HitRecord};
+
+use std::sync::Arc;
+
+/// Closed object with inside and outside, e.g. sphere or mesh declared `Watertight`, which `Csg` can combine.
+pub trait Solid: Hit {
+    /// Returns every crossing of the surface after `t_min`, ordered by `t`.\
+    /// Crossings with front face enter the object, crossings with back face leave it,
+    /// so the ray is inside between each entry and following exit.\
+    /// By default crossings are found one after another by `Hit::hit`.
+    fn crossings(&self, ray: &Ray, t_min: f64) -> Vec<HitRecord<'_>> {
+        successive_hits(self, ray, t_min)
+    }
+}
+
+/// Returns every hit of `shape` after `t_min`, found one after another.
+pub(crate) fn successive_hits<'a, H: Hit + ?Sized>(shape: &'a H, ray: &Ray, t_min: f64) -> Vec<HitRecord<'a>> {
+    let mut crossings = Vec::new();
+    let mut t_min = t_min;
+    while let Some(hit_record) = shape.hit(ray, t_min, f64::INFINITY) {
+        // Steps over the surface just hit, also when it is shared by neighbouring triangles of mesh.
+        t_min = hit_record.t() + 1e-9 * hit_record.t().abs().max(1.0);
+        crossings.push(hit_record);
+    }
+
+    crossings
+}
+
+impl<H: Solid + ?Sized> Solid for Arc<H> {
+    fn crossings(&self, ray: &Ray, t_min: f64) -> Vec<HitRecord<'_>> {
+        (**self).crossings(ray, t_min)
+    }
+}
@@ -0,0 +1,150 @@
+use crate::rgb::RGB;
+
+use super::{invalid_data, parse_dimension, Image};
+
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Reads image in Radiance `.hdr` (RGBE) format.
+pub fn read_hdr(reader: impl Read) -> io::Result<Image> {
+    let mut reader = BufReader::new(reader);
+    let mut line = String::new();
+
+    reader.read_line(&mut line)?;
+    if !line.starts_with("#?") {
+        return Err(invalid_data("missing Radiance signature"));
+    }
+
+    loop {
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid_data("unexpected end of header"));
+        }
+
+        let line = line.trim();
+        if line.is_empty() {
+            break;
+        }
+
+        if let Some(format) = line.strip_prefix("FORMAT=") {
+            if format != "32-bit_rle_rgbe" {
+                return Err(invalid_data("unsupported pixel format"));
+            }
+        }
+    }
+
+    line.clear();
+    reader.read_line(&mut line)?;
+    let (width, height) = match line.split_whitespace().collect::<Vec<&str>>()[..] {
+        ["-Y", height, "+X", width] => (parse_dimension(width)?, parse_dimension(height)?),
+        _ => return Err(invalid_data("unsupported image orientation"))
+    };
+
+    let mut pixels = Vec::with_capacity(width * height);
+    let mut scanline = vec![[0u8; 4]; width];
+    for _ in 0..height {
+        read_scanline(&mut reader, &mut scanline)?;
+        pixels.extend(scanline.iter().map(rgbe_to_rgb));
+    }
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+        gamma_encoded: false,
+        alpha: None
+    })
+}
+
+fn rgbe_to_rgb(rgbe: &[u8; 4]) -> RGB {
+    if rgbe[3] == 0 {
+        return RGB::default();
+    }
+
+    let f = 2.0f64.powi(rgbe[3] as i32 - 136);
+    RGB(rgbe[0] as f64 * f, rgbe[1] as f64 * f, rgbe[2] as f64 * f)
+}
+
+fn read_scanline(reader: &mut impl Read, scanline: &mut [[u8; 4]]) -> io::Result<()> {
+    let width = scanline.len();
+    let mut first = [0u8; 4];
+    reader.read_exact(&mut first)?;
+
+    let is_run_length_encoded = (8..0x8000).contains(&width) && first[0] == 2 && first[1] == 2 && first[2] < 0x80;
+    if !is_run_length_encoded {
+        return read_flat_scanline(reader, first, scanline);
+    }
+
+    if ((first[2] as usize) << 8 | first[3] as usize) != width {
+        return Err(invalid_data("scanline width mismatch"));
+    }
+
+    for channel in 0..4 {
+        let mut x = 0;
+        while x < width {
+            let mut count = [0u8; 1];
+            reader.read_exact(&mut count)?;
+
+            if count[0] > 128 {
+                let run = (count[0] - 128) as usize;
+                if run > width - x {
+                    return Err(invalid_data("run overflows scanline"));
+                }
+
+                let mut value = [0u8; 1];
+                reader.read_exact(&mut value)?;
+                for pixel in &mut scanline[x..x + run] {
+                    pixel[channel] = value[0];
+                }
+                x += run;
+            } else {
+                let count = count[0] as usize;
+                if count == 0 || count > width - x {
+                    return Err(invalid_data("invalid scanline data"));
+                }
+
+                let mut values = [0u8; 128];
+                reader.read_exact(&mut values[..count])?;
+                for (pixel, value) in scanline[x..x + count].iter_mut().zip(&values[..count]) {
+                    pixel[channel] = *value;
+                }
+                x += count;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn read_flat_scanline(reader: &mut impl Read, first: [u8; 4], scanline: &mut [[u8; 4]]) -> io::Result<()> {
+    let mut pixel = first;
+    let (mut x, mut shift) = (0, 0);
+
+    loop {
+        if pixel[0] == 1 && pixel[1] == 1 && pixel[2] == 1 {
+            // Old-style run: repeat previous pixel.
+            if x == 0 || shift > 16 {
+                return Err(invalid_data("invalid run in scanline"));
+            }
+
+            let count = (pixel[3] as usize) << shift;
+            if count > scanline.len() - x {
+                return Err(invalid_data("run overflows scanline"));
+            }
+
+            let previous = scanline[x - 1];
+            scanline[x..x + count].fill(previous);
+            x += count;
+            shift += 8;
+        } else {
+            scanline[x] = pixel;
+            x += 1;
+            shift = 0;
+        }
+
+        if x == scanline.len() {
+            return Ok(());
+        }
+
+        reader.read_exact(&mut pixel)?;
+    }
+}
@@ -0,0 +1,44 @@
+mod hdr_reader;
+mod ppm_reader;
+mod png_reader;
+
+pub use {hdr_reader::read_hdr, ppm_reader::read_ppm, png_reader::read_png};
+
+use crate::RGB;
+
+use std::{fs::File, io::{self, BufReader}, path::Path};
+
+/// Decoded image with pixels starting from upper left corner.
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub pixels: Vec<RGB>,
+    /// True if components are stored gamma-corrected (8-bit and 16-bit formats) rather than linear.
+    pub gamma_encoded: bool,
+    /// Opacity of pixels if the format stores it.
+    pub alpha: Option<Vec<f64>>
+}
+
+/// Reads image choosing format by file extension (`.ppm`, `.pgm`, `.png` or `.hdr`).
+pub fn read_image(path: &Path) -> io::Result<Image> {
+    let extension = path.extension().and_then(|extension| extension.to_str()).map(|extension| extension.to_ascii_lowercase());
+    let reader = BufReader::new(File::open(path)?);
+
+    match extension.as_deref() {
+        Some("ppm" | "pgm" | "pnm") => read_ppm(reader),
+        Some("png") => read_png(reader),
+        Some("hdr") => read_hdr(reader),
+        _ => Err(io::Error::new(io::ErrorKind::Unsupported, "unsupported image format"))
+    }
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message)
+}
+
+fn parse_dimension(value: &str) -> io::Result<usize> {
+    match value.parse::<usize>() {
+        Ok(dimension) if dimension > 0 => Ok(dimension),
+        _ => Err(invalid_data("invalid image dimension"))
+    }
+}
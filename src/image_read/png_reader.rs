@@ -0,0 +1,39 @@
+use crate::rgb::RGB;
+
+use super::{invalid_data, Image};
+
+use std::io::{self, Read};
+
+/// Reads image in PNG format. Palette and low bit depth images are expanded, alpha channel is kept separately.
+pub fn read_png(reader: impl Read) -> io::Result<Image> {
+    let mut decoder = png::Decoder::new(reader);
+    decoder.set_transformations(png::Transformations::EXPAND);
+
+    let mut reader = decoder.read_info().map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+    let mut buf = vec![0; reader.output_buffer_size()];
+    let info = reader.next_frame(&mut buf).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+    let channels = info.color_type.samples();
+    let samples = match info.bit_depth {
+        png::BitDepth::Eight => buf[..info.buffer_size()].iter().map(|&sample| sample as f64 / 255.0).collect::<Vec<f64>>(),
+        png::BitDepth::Sixteen => buf[..info.buffer_size()].chunks_exact(2).map(|sample| u16::from_be_bytes([sample[0], sample[1]]) as f64 / 65535.0).collect(),
+        _ => return Err(invalid_data("unsupported bit depth"))
+    };
+
+    let (width, height) = (info.width as usize, info.height as usize);
+    let pixels = samples.chunks_exact(channels).take(width * height).map(|sample| {
+        if channels < 3 { RGB(sample[0], sample[0], sample[0]) } else { RGB(sample[0], sample[1], sample[2]) }
+    }).collect();
+
+    let alpha = (channels == 2 || channels == 4).then(|| {
+        samples.chunks_exact(channels).take(width * height).map(|sample| sample[channels - 1]).collect()
+    });
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+        gamma_encoded: true,
+        alpha
+    })
+}
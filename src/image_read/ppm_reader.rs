@@ -0,0 +1,84 @@
+use crate::rgb::RGB;
+
+use super::{invalid_data, parse_dimension, Image};
+
+use std::io::{self, Read};
+
+/// Reads image in Netpbm format: `P2`/`P5` (grayscale) or `P3`/`P6` (color).
+pub fn read_ppm(mut reader: impl Read) -> io::Result<Image> {
+    let mut bytes = Vec::new();
+    reader.read_to_end(&mut bytes)?;
+
+    let mut position = 0;
+    let magic = next_token(&bytes, &mut position)?;
+    let (channels, binary) = match magic {
+        "P2" => (1, false),
+        "P3" => (3, false),
+        "P5" => (1, true),
+        "P6" => (3, true),
+        _ => return Err(invalid_data("unsupported Netpbm format"))
+    };
+
+    let width = parse_dimension(next_token(&bytes, &mut position)?)?;
+    let height = parse_dimension(next_token(&bytes, &mut position)?)?;
+    let max_value = parse_dimension(next_token(&bytes, &mut position)?)?;
+    if max_value > u16::MAX as usize {
+        return Err(invalid_data("invalid maximum value"));
+    }
+
+    let sample_count = width * height * channels;
+    let samples = if binary {
+        // Single whitespace separates header from binary data.
+        position += 1;
+        let sample_size = if max_value > 255 { 2 } else { 1 };
+        let data = bytes.get(position..position + sample_count * sample_size).ok_or_else(|| invalid_data("unexpected end of data"))?;
+
+        if sample_size == 2 {
+            data.chunks_exact(2).map(|sample| u16::from_be_bytes([sample[0], sample[1]]) as usize).collect::<Vec<usize>>()
+        } else {
+            data.iter().map(|&sample| sample as usize).collect()
+        }
+    } else {
+        let mut samples = Vec::with_capacity(sample_count);
+        for _ in 0..sample_count {
+            samples.push(next_token(&bytes, &mut position)?.parse::<usize>().map_err(|_| invalid_data("invalid sample"))?);
+        }
+        samples
+    };
+
+    let scale = 1.0 / max_value as f64;
+    let pixels = samples.chunks_exact(channels).map(|sample| {
+        let value = |index: usize| sample[index].min(max_value) as f64 * scale;
+        if channels == 1 { RGB(value(0), value(0), value(0)) } else { RGB(value(0), value(1), value(2)) }
+    }).collect();
+
+    Ok(Image {
+        width,
+        height,
+        pixels,
+        gamma_encoded: true,
+        alpha: None
+    })
+}
+
+fn next_token<'a>(bytes: &'a [u8], position: &mut usize) -> io::Result<&'a str> {
+    loop {
+        match bytes.get(*position) {
+            Some(b'#') => {
+                while !matches!(bytes.get(*position), Some(b'\n') | None) {
+                    *position += 1;
+                }
+            },
+            Some(byte) if byte.is_ascii_whitespace() => *position += 1,
+            Some(_) => break,
+            None => return Err(invalid_data("unexpected end of data"))
+        }
+    }
+
+    let start = *position;
+    while bytes.get(*position).is_some_and(|byte| !byte.is_ascii_whitespace()) {
+        *position += 1;
+    }
+
+    std::str::from_utf8(&bytes[start..*position]).map_err(|_| invalid_data("invalid header"))
+}
@@ -5,16 +5,31 @@ mod hit;
 mod bound;
 mod scatter;
 mod bvh;
+mod background;
+mod distance;
+
+/// Backgrounds which light the scene from far away.
+pub mod backgrounds;
+
+/// Signed distance functions which `shapes::Sdf` ray-marches.
+pub mod distances;
 
 /// Simple materials which scatter light.
 pub mod materials;
 
+mod texture;
+
+/// Textures which vary surface properties.
+pub mod textures;
+
 /// Math needed for ray tracing.
 pub mod math;
 
 mod renderer;
 mod image_write;
+mod image_read;
 mod rgb;
+mod spectrum;
 mod camera;
 
 /// List of hittable shapes.
@@ -24,10 +39,13 @@ mod random;
 
 pub use {camera::Camera,
          image_write::{ImageWrite, P3ImageWriter},
-         hit::{Hit, HitRecord},
+         hit::{Hit, HitRecord, Solid},
          bound::{Interval, AABB},
          bvh::BVHNode,
          scatter::Scatter,
+         texture::Texture,
+         background::Background,
+         distance::Distance,
          renderer::Renderer,
          rgb::RGB,
          scene::Scene};
@@ -0,0 +1,82 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use std::sync::Arc;
+
+/// Wraps any material and tilts its shading normal as if the surface was displaced along the normal by height texture.\
+/// Height is luminance of the texture multiplied by `strength`, so both image and procedural textures (e.g. `Noise`) can be used.
+/// ```
+/// use rayimg::{materials::{BumpMap, Lambertian}, textures::Noise, RGB};
+///
+/// let material = BumpMap::new(Lambertian::new(RGB(0.8, 0.8, 0.8)), Noise::perlin(4.0)).strength(0.05);
+/// ```
+pub struct BumpMap<'a> {
+    material: Arc<dyn Scatter + 'a + Send + Sync>,
+    height: Arc<dyn Texture + 'a + Send + Sync>,
+    strength: f64
+}
+
+impl<'a> BumpMap<'a> {
+    const DELTA: f64 = 1e-3;
+
+    /// Creates new `BumpMap` over `material`.
+    pub fn new(material: impl Scatter + 'a + Send + Sync, height: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            material: Arc::new(material),
+            height: Arc::new(height),
+            strength: 1.0
+        }
+    }
+
+    /// Sets multiplier of heights. Default is 1.0.
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    fn height(&self, u: f64, v: f64, point: Vec3<f64>) -> f64 {
+        self.height.value(u, v, point).luminance() * self.strength
+    }
+
+    fn perturb<'b>(&self, hit_record: &HitRecord<'b>) -> HitRecord<'b> {
+        let outward = if hit_record.front_face() { hit_record.normal() } else { -hit_record.normal() };
+        let (dpdu, dpdv) = hit_record.tangents();
+        let ((u, v), point) = (hit_record.uv(), hit_record.point());
+
+        // Forward differences of height, moving hit point along with surface coordinates for solid textures.
+        let height = self.height(u, v, point);
+        let du = (self.height(u + Self::DELTA, v, point + dpdu * Self::DELTA) - height) / Self::DELTA;
+        let dv = (self.height(u, v + Self::DELTA, point + dpdv * Self::DELTA) - height) / Self::DELTA;
+
+        let normal = (dpdu + outward * du).cross(&(dpdv + outward * dv));
+        if normal.near_epsilon(1e-12) {
+            return *hit_record;
+        }
+
+        let normal = normal.normalize();
+        let mut perturbed = *hit_record;
+        perturbed.set_shading_normal(if normal.dot(&outward) < 0.0 { -normal } else { normal });
+        perturbed
+    }
+}
+
+impl<'a> Scatter for BumpMap<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        self.material.scatter(ray, &self.perturb(hit_record))
+    }
+
+    fn emit(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        self.material.emit(ray, hit_record)
+    }
+
+    fn emission(&self) -> RGB {
+        self.material.emission()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.material.is_dispersive()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        self.material.evaluate(ray, &self.perturb(hit_record), direction)
+    }
+}
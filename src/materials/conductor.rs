@@ -0,0 +1,97 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3, ONB}, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use super::microfacet::{fresnel_conductor, TrowbridgeReitz};
+
+use std::sync::Arc;
+
+/// Metal described by complex index of refraction `eta + i * k` with GGX microfacet roughness.\
+/// Reflected directions are chosen from distribution of visible microfacet normals, so rough metals
+/// conserve energy and can be combined with light sampling.
+/// ```
+/// use rayimg::{materials::Conductor, math::{Ray, Vec3}, HitRecord, Scatter};
+///
+/// let gold = Conductor::gold(0.3);
+/// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+/// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+///
+/// let (color, pdf) = gold.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, 1.0)).unwrap();
+/// assert!(color.r() > color.b() && pdf > 0.0);
+/// ```
+pub struct Conductor<'a> {
+    eta: RGB,
+    k: RGB,
+    roughness: Arc<dyn Texture + 'a + Send + Sync>
+}
+
+impl<'a> Conductor<'a> {
+    /// Creates new `Conductor` from real and imaginary parts of index of refraction for red, green and blue light.
+    /// Roughness is luminance of the texture clamped to `0.0..=1.0`, 0.0 is perfect mirror.
+    pub fn new(eta: RGB, k: RGB, roughness: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            eta,
+            k,
+            roughness: Arc::new(roughness)
+        }
+    }
+
+    /// Creates gold `Conductor`.
+    pub fn gold(roughness: impl Texture + 'a + Send + Sync) -> Self {
+        Self::new(RGB(0.143, 0.374, 1.442), RGB(3.983, 2.385, 1.603), roughness)
+    }
+
+    /// Creates copper `Conductor`.
+    pub fn copper(roughness: impl Texture + 'a + Send + Sync) -> Self {
+        Self::new(RGB(0.200, 0.924, 1.102), RGB(3.912, 2.452, 2.142), roughness)
+    }
+
+    /// Creates aluminum `Conductor`.
+    pub fn aluminum(roughness: impl Texture + 'a + Send + Sync) -> Self {
+        Self::new(RGB(1.657, 0.880, 0.521), RGB(9.224, 6.270, 4.837), roughness)
+    }
+
+    fn distribution(&self, hit_record: &HitRecord) -> TrowbridgeReitz {
+        let (u, v) = hit_record.uv();
+        TrowbridgeReitz::from_roughness(self.roughness.value(u, v, hit_record.point()).luminance())
+    }
+}
+
+impl<'a> Scatter for Conductor<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let onb = ONB::new(hit_record.normal());
+        let wo = onb.project(-ray.direction().normalize());
+        if wo.z <= 0.0 {
+            return None;
+        }
+
+        let distribution = self.distribution(hit_record);
+        let h = if distribution.is_smooth() { Vec3::new(0.0, 0.0, 1.0) } else { distribution.sample_visible_normal(wo) };
+        let wi = (-wo).reflect(h);
+        if wi.z <= 0.0 {
+            return None;
+        }
+
+        let fresnel = fresnel_conductor(wo.dot(&h), self.eta, self.k);
+        let attenuation = if distribution.is_smooth() { fresnel } else { fresnel * (distribution.g2(wo, wi) / distribution.g1(wo)) };
+        Some((Ray::new(hit_record.point(), onb.local(wi)), attenuation))
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let distribution = self.distribution(hit_record);
+        if distribution.is_smooth() {
+            return None;
+        }
+
+        let onb = ONB::new(hit_record.normal());
+        let (wo, wi) = (onb.project(-ray.direction().normalize()), onb.project(direction.normalize()));
+        if wo.z <= 0.0 || wi.z <= 0.0 {
+            return Some((RGB::default(), 0.0));
+        }
+
+        let h = (wo + wi).normalize();
+        let d = distribution.d(h);
+        let fresnel = fresnel_conductor(wo.dot(&h), self.eta, self.k);
+
+        Some((fresnel * (d * distribution.g2(wo, wi) / (4.0 * wo.z)), distribution.visible_normal_pdf(wo, h) / (4.0 * wo.dot(&h))))
+    }
+}
@@ -1,44 +1,327 @@
-use crate::{rgb::RGB, scatter::Scatter, math::Ray, hit::HitRecord, random::random_in_range};
+use crate::{rgb::RGB, scatter::Scatter, texture::Texture, math::{Ray, Vec3, ONB}, hit::HitRecord, random::random_in_range, spectrum::{wavelength_weight, WAVELENGTH_MAX, WAVELENGTH_MIN}};
 
-/// Material that sometimes reflects and sometimes refracts.
-pub struct Dielectric {
-    albedo: RGB,
-    refraction_index: f64
+use super::{microfacet::{fresnel_dielectric, TrowbridgeReitz}, thin_film::{Substrate, ThinFilm}};
+
+use std::sync::Arc;
+
+/// Dependence of refraction index on wavelength of light.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Dispersion {
+    /// Cauchy equation `n = a + b / λ²` with wavelength `λ` in micrometers.
+    Cauchy {
+        a: f64,
+        b: f64
+    },
+    /// Sellmeier equation `n² = 1 + Σ bᵢλ² / (λ² - cᵢ)` with wavelength `λ` in micrometers.
+    Sellmeier {
+        b: [f64; 3],
+        c: [f64; 3]
+    }
+}
+
+impl Dispersion {
+    /// Borosilicate crown glass (Schott N-BK7).
+    pub const BK7: Self = Self::Sellmeier {
+        b: [1.03961212, 0.231792344, 1.01046945],
+        c: [0.00600069867, 0.0200179144, 103.560653]
+    };
+
+    /// Diamond.
+    pub const DIAMOND: Self = Self::Sellmeier {
+        b: [0.3306, 4.3356, 0.0],
+        c: [0.030625, 0.011236, 0.0]
+    };
+
+    /// Returns refraction index of light with `wavelength` in nanometers.
+    /// ```
+    /// # use rayimg::materials::Dispersion;
+    /// assert!((Dispersion::BK7.refraction_index(587.6) - 1.5168).abs() < 1e-4);
+    /// assert!(Dispersion::DIAMOND.refraction_index(400.0) > Dispersion::DIAMOND.refraction_index(700.0));
+    /// ```
+    pub fn refraction_index(&self, wavelength: f64) -> f64 {
+        let micrometers = wavelength * 1e-3;
+        let squared = micrometers * micrometers;
+
+        match self {
+            Self::Cauchy { a, b } => a + b / squared,
+            Self::Sellmeier { b, c } => (1.0 + (0..3).map(|index| b[index] * squared / (squared - c[index])).sum::<f64>()).sqrt()
+        }
+    }
 }
 
-impl Dielectric {
-    /// Creates new Dielectric material.
-    pub fn new(albedo: RGB, refraction_index: f64) -> Self {
+/// Material that sometimes reflects and sometimes refracts.\
+/// Surface is smooth by default, rough glass uses GGX microfacets for both reflection and refraction.
+/// Light travelling inside can be absorbed according to Beer-Lambert law, and dispersion splits white light
+/// into rays of single wavelengths.
+pub struct Dielectric<'a> {
+    albedo: Arc<dyn Texture + 'a + Send + Sync>,
+    refraction_index: f64,
+    roughness: Option<Arc<dyn Texture + 'a + Send + Sync>>,
+    absorption: RGB,
+    dispersion: Option<Dispersion>,
+    thin_film: Option<ThinFilm>
+}
+
+impl<'a> Dielectric<'a> {
+    /// Creates new Dielectric material. Albedo is any `Texture`, e.g. constant `RGB`.
+    pub fn new(albedo: impl Texture + 'a + Send + Sync, refraction_index: f64) -> Self {
         Self {
-            albedo,
-            refraction_index
+            albedo: Arc::new(albedo),
+            refraction_index,
+            roughness: None,
+            absorption: RGB::default(),
+            dispersion: None,
+            thin_film: None
         }
     }
 
+    /// Sets roughness of the surface, luminance of the texture clamped to `0.0..=1.0`.
+    /// ```
+    /// use rayimg::{materials::Dielectric, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+    ///
+    /// let frosted_glass = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.3);
+    /// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+    /// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    /// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+    ///
+    /// let (transmitted, _) = frosted_glass.evaluate(&ray, &hit_record, Vec3::new(0.1, 0.0, -1.0)).unwrap();
+    /// let (reflected, _) = frosted_glass.evaluate(&ray, &hit_record, Vec3::new(0.1, 0.0, 1.0)).unwrap();
+    /// assert!(transmitted.r() > reflected.r());
+    /// ```
+    pub fn roughness(mut self, roughness: impl Texture + 'a + Send + Sync) -> Self {
+        self.roughness = Some(Arc::new(roughness));
+        self
+    }
+
+    /// Sets absorption coefficient of the medium inside per unit of distance, so thick glass is tinted more than thin.
+    /// ```
+    /// use rayimg::{materials::Dielectric, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+    ///
+    /// let green_glass = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).absorption(RGB(1.0, 0.1, 1.0));
+    /// let mut hit_record = HitRecord::new(2.0, Vec3::new(0.0, 0.0, -2.0));
+    /// let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    /// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, -1.0));
+    ///
+    /// let (_, color) = green_glass.scatter(&ray, &hit_record).unwrap();
+    /// assert!((color.r() - (-2.0f64).exp()).abs() < 1e-9 && (color.g() - (-0.2f64).exp()).abs() < 1e-9);
+    /// ```
+    pub fn absorption(mut self, absorption: RGB) -> Self {
+        self.absorption = absorption;
+        self
+    }
+
+    /// Sets dependence of refraction index on wavelength, replacing constant refraction index.
+    /// White rays entering the material are given random wavelength and tinted by its color.
+    pub fn dispersion(mut self, dispersion: Dispersion) -> Self {
+        self.dispersion = Some(dispersion);
+        self
+    }
+
+    /// Coats the surface with `ThinFilm`, e.g. soap bubble or oil on water, replacing its Fresnel reflectance.
+    /// ```
+    /// use rayimg::{materials::{Dielectric, ThinFilm}, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+    ///
+    /// let soap_bubble = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.0).thin_film(ThinFilm::new(400.0, 1.33)).roughness(0.2);
+    /// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    /// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+    /// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+    ///
+    /// let (reflected, _) = soap_bubble.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, 1.0)).unwrap();
+    /// assert!(reflected.r() != reflected.b());
+    /// ```
+    pub fn thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
+
     fn reflectance(cosine: f64, refraction_index: f64) -> f64 {
         let mut r0 = (1.0 - refraction_index) / (1.0 + refraction_index);
         r0 = r0 * r0;
         r0 + (1.0 - r0) * (1.0 - cosine).powi(5)
     }
-}
 
-impl Scatter for Dielectric {
-    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+    fn albedo(&self, hit_record: &HitRecord) -> RGB {
+        let (u, v) = hit_record.uv();
+        self.albedo.value(u, v, hit_record.point())
+    }
+
+    /// Returns GGX distribution of rough surface, or None if it is smooth.
+    fn distribution(&self, hit_record: &HitRecord) -> Option<TrowbridgeReitz> {
+        let (u, v) = hit_record.uv();
+        let roughness = self.roughness.as_ref()?.value(u, v, hit_record.point()).luminance();
+        Some(TrowbridgeReitz::from_roughness(roughness)).filter(|distribution| !distribution.is_smooth())
+    }
+
+    fn refraction_index(&self, ray: &Ray) -> f64 {
+        match (self.dispersion, ray.wavelength()) {
+            (Some(dispersion), Some(wavelength)) => dispersion.refraction_index(wavelength),
+            _ => self.refraction_index
+        }
+    }
+
+    /// Returns ratio of refraction indices of transmitted and incident sides.
+    fn eta(&self, ray: &Ray, hit_record: &HitRecord) -> f64 {
+        let refraction_index = self.refraction_index(ray);
+        if hit_record.front_face() { refraction_index } else { 1.0 / refraction_index }
+    }
+
+    /// Returns reflectance of the boundary for light with cosine `cos_i` to the normal (of the surface or microfacet)
+    /// and probability of choosing reflection.
+    fn fresnel(&self, ray: &Ray, hit_record: &HitRecord, cos_i: f64, eta: f64) -> (RGB, f64) {
+        let Some(thin_film) = &self.thin_film else {
+            let fresnel = fresnel_dielectric(cos_i, eta);
+            return (RGB(fresnel, fresnel, fresnel), fresnel);
+        };
+
+        // Film coats the outer side, so its reflectance is found for the angle outside.
+        let refraction_index = self.refraction_index(ray);
+        let cos_outside = if hit_record.front_face() {
+            cos_i
+        } else {
+            let sin2 = (1.0 - cos_i * cos_i) * refraction_index * refraction_index;
+            if sin2 >= 1.0 {
+                return (RGB(1.0, 1.0, 1.0), 1.0);
+            }
+            (1.0 - sin2).sqrt()
+        };
+
+        let reflectance = thin_film.reflectance(cos_outside, &Substrate::Dielectric(refraction_index), ray.wavelength());
+        (reflectance, (reflectance.0 + reflectance.1 + reflectance.2) / 3.0)
+    }
+
+    /// Returns fraction of light not absorbed on its way from `ray` origin to the hit point inside the material.
+    fn transmittance(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        if hit_record.front_face() {
+            return RGB(1.0, 1.0, 1.0);
+        }
+
+        let distance = hit_record.t() * ray.direction().len();
+        RGB((-self.absorption.0 * distance).exp(), (-self.absorption.1 * distance).exp(), (-self.absorption.2 * distance).exp())
+    }
+
+    fn scatter_smooth(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
         let unit_direction = ray.direction().normalize();
         let normal = hit_record.normal();
         
-        let refraction_ratio = if hit_record.front_face() { 1.0 / self.refraction_index } else { self.refraction_index };
+        let refraction_ratio = 1.0 / self.eta(ray, hit_record);
 
         let cos_theta = (-unit_direction).dot(&normal);
         let sin_theta = (1.0 - cos_theta * cos_theta).sqrt();
 
         let cannot_refract = (refraction_ratio * sin_theta) > 1.0;
-        let direction = if cannot_refract || Dielectric::reflectance(cos_theta, refraction_ratio) > random_in_range(0.0..1.0) {
-            unit_direction.reflect(normal)
+        let (direction, weight) = if cannot_refract {
+            (unit_direction.reflect(normal), RGB(1.0, 1.0, 1.0))
+        } else if self.thin_film.is_some() {
+            let (reflectance, probability) = self.fresnel(ray, hit_record, cos_theta, 1.0 / refraction_ratio);
+            if probability > random_in_range(0.0..1.0) {
+                (unit_direction.reflect(normal), reflectance / probability)
+            } else {
+                (unit_direction.refract(normal, refraction_ratio), (RGB(1.0, 1.0, 1.0) - reflectance) / (1.0 - probability))
+            }
+        } else if Dielectric::reflectance(cos_theta, refraction_ratio) > random_in_range(0.0..1.0) {
+            (unit_direction.reflect(normal), RGB(1.0, 1.0, 1.0))
+        } else {
+            (unit_direction.refract(normal, refraction_ratio), RGB(1.0, 1.0, 1.0))
+        };
+
+        Some((Ray::new(hit_record.point(), direction), self.albedo(hit_record) * weight))
+    }
+
+    fn scatter_rough(&self, ray: &Ray, hit_record: &HitRecord, distribution: TrowbridgeReitz) -> Option<(Ray, RGB)> {
+        let onb = ONB::new(hit_record.normal());
+        let wo = onb.project(-ray.direction().normalize());
+        if wo.z <= 0.0 {
+            return None;
+        }
+
+        let eta = self.eta(ray, hit_record);
+        let h = distribution.sample_visible_normal(wo);
+        let (reflectance, probability) = self.fresnel(ray, hit_record, wo.dot(&h), eta);
+        let (wi, weight) = if probability > random_in_range(0.0..1.0) {
+            ((-wo).reflect(h), reflectance / probability)
         } else {
-            unit_direction.refract(normal, refraction_ratio)
+            ((-wo).refract(h, 1.0 / eta), (RGB(1.0, 1.0, 1.0) - reflectance) / (1.0 - probability))
+        };
+
+        // Microfacet may send light to the wrong side of macro surface, it is lost then.
+        let transmitted = wi.z < 0.0;
+        if transmitted != (wi.dot(&h) < 0.0) {
+            return None;
+        }
+
+        let attenuation = self.albedo(hit_record) * weight * (distribution.g2(wo, wi) / distribution.g1(wo));
+        Some((Ray::new(hit_record.point(), onb.local(wi)), attenuation))
+    }
+}
+
+impl<'a> Scatter for Dielectric<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        // White light is split by choosing one wavelength, which stays with the ray afterwards.
+        let (ray, weight) = match (self.dispersion, ray.wavelength()) {
+            (Some(_), None) => {
+                let wavelength = random_in_range(WAVELENGTH_MIN..WAVELENGTH_MAX);
+                (ray.with_wavelength(wavelength), wavelength_weight(wavelength))
+            },
+            _ => (*ray, RGB(1.0, 1.0, 1.0))
+        };
+
+        let (scattered, color) = match self.distribution(hit_record) {
+            Some(distribution) => self.scatter_rough(&ray, hit_record, distribution),
+            None => self.scatter_smooth(&ray, hit_record)
+        }?;
+
+        let scattered = match ray.wavelength() {
+            Some(wavelength) => scattered.with_wavelength(wavelength),
+            None => scattered
         };
 
-        Some((Ray::new(hit_record.point(), direction), self.albedo))
+        Some((scattered, color * weight * self.transmittance(&ray, hit_record)))
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.dispersion.is_some() || self.thin_film.is_some()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        if self.dispersion.is_some() && ray.wavelength().is_none() {
+            return None;
+        }
+
+        let distribution = self.distribution(hit_record)?;
+
+        let onb = ONB::new(hit_record.normal());
+        let (wo, wi) = (onb.project(-ray.direction().normalize()), onb.project(direction.normalize()));
+        if wo.z <= 0.0 || wi.z == 0.0 {
+            return Some((RGB::default(), 0.0));
+        }
+
+        let eta = self.eta(ray, hit_record);
+        let albedo = self.albedo(hit_record) * self.transmittance(ray, hit_record);
+
+        if wi.z > 0.0 {
+            let h = (wo + wi).normalize();
+            let (reflectance, probability) = self.fresnel(ray, hit_record, wo.dot(&h), eta);
+            let d = distribution.d(h);
+
+            let value = albedo * reflectance * (d * distribution.g2(wo, wi) / (4.0 * wo.z));
+            return Some((value, probability * distribution.visible_normal_pdf(wo, h) / (4.0 * wo.dot(&h))));
+        }
+
+        // Generalized half vector of refraction.
+        let mut h = (wi * eta + wo).normalize();
+        if h.z < 0.0 {
+            h = -h;
+        }
+        if wo.dot(&h) <= 0.0 || wi.dot(&h) >= 0.0 {
+            return Some((RGB::default(), 0.0));
+        }
+
+        let (reflectance, probability) = self.fresnel(ray, hit_record, wo.dot(&h), eta);
+        let denominator = (wi.dot(&h) + wo.dot(&h) / eta).powi(2);
+        let jacobian = wi.dot(&h).abs() / denominator;
+        let d = distribution.d(h);
+
+        let value = albedo * (RGB(1.0, 1.0, 1.0) - reflectance) * (d * distribution.g2(wo, wi) * wo.dot(&h) * jacobian / wo.z);
+        Some((value, (1.0 - probability) * distribution.visible_normal_pdf(wo, h) * jacobian))
     }
 }
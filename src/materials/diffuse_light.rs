@@ -0,0 +1,34 @@
+use crate::{hit::HitRecord, rgb::RGB, scatter::Scatter, math::Ray};
+
+/// Material that emits light of some color from the front face and does not scatter.\
+/// Shapes with this material can be passed to `RendererBuilder::lights` to be sampled directly.
+pub struct DiffuseLight {
+    emission: RGB
+}
+
+impl DiffuseLight {
+    /// Creates new DiffuseLight material. Components of `emission` may be greater than 1.0.
+    pub fn new(emission: RGB) -> Self {
+        Self {
+            emission
+        }
+    }
+}
+
+impl Scatter for DiffuseLight {
+    fn scatter(&self, _: &Ray, _: &HitRecord) -> Option<(Ray, RGB)> {
+        None
+    }
+
+    fn emission(&self) -> RGB {
+        self.emission
+    }
+
+    fn emit(&self, _: &Ray, hit_record: &HitRecord) -> RGB {
+        if hit_record.front_face() {
+            self.emission
+        } else {
+            RGB::default()
+        }
+    }
+}
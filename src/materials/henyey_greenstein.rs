@@ -0,0 +1,66 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3, ONB}, random::random_in_range, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use std::{f64::consts::PI, sync::Arc};
+
+/// Henyey-Greenstein phase function of participating media.\
+/// Asymmetry `g` in `-1.0..1.0` is average cosine of scattering angle: positive values scatter light forward
+/// (e.g. fog and clouds), negative backward and 0.0 is isotropic.
+/// ```
+/// use rayimg::{materials::HenyeyGreenstein, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+///
+/// let fog = HenyeyGreenstein::new(RGB(1.0, 1.0, 1.0), 0.7);
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+/// let hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, -1.0));
+///
+/// let (_, forward) = fog.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, -1.0)).unwrap();
+/// let (_, backward) = fog.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, 1.0)).unwrap();
+/// assert!(forward > backward);
+/// ```
+pub struct HenyeyGreenstein<'a> {
+    albedo: Arc<dyn Texture + 'a + Send + Sync>,
+    g: f64
+}
+
+impl<'a> HenyeyGreenstein<'a> {
+    /// Creates new `HenyeyGreenstein` phase function with asymmetry `g`, clamped to `-0.99..=0.99`.
+    pub fn new(albedo: impl Texture + 'a + Send + Sync, g: f64) -> Self {
+        Self {
+            albedo: Arc::new(albedo),
+            g: g.clamp(-0.99, 0.99)
+        }
+    }
+
+    fn albedo(&self, hit_record: &HitRecord) -> RGB {
+        let (u, v) = hit_record.uv();
+        self.albedo.value(u, v, hit_record.point())
+    }
+
+    /// Returns phase function value of cosine between propagation directions before and after scattering.
+    fn phase(&self, cos_theta: f64) -> f64 {
+        let denominator = 1.0 + self.g * self.g - 2.0 * self.g * cos_theta;
+        0.25 / PI * (1.0 - self.g * self.g) / (denominator * denominator.sqrt())
+    }
+}
+
+impl<'a> Scatter for HenyeyGreenstein<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let xi = random_in_range(0.0..1.0);
+        let cos_theta = if self.g.abs() < 1e-3 {
+            1.0 - 2.0 * xi
+        } else {
+            let square = (1.0 - self.g * self.g) / (1.0 - self.g + 2.0 * self.g * xi);
+            ((1.0 + self.g * self.g - square * square) / (2.0 * self.g)).clamp(-1.0, 1.0)
+        };
+
+        let sin_theta = (1.0 - cos_theta * cos_theta).max(0.0).sqrt();
+        let phi = 2.0 * PI * random_in_range(0.0..1.0);
+        let direction = ONB::new(ray.direction()).local(Vec3::new(sin_theta * phi.cos(), sin_theta * phi.sin(), cos_theta));
+
+        Some((Ray::new(hit_record.point(), direction), self.albedo(hit_record)))
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let phase = self.phase(ray.direction().normalize().dot(&direction.normalize()));
+        Some((self.albedo(hit_record) * phase, phase))
+    }
+}
@@ -0,0 +1,33 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use std::{f64::consts::PI, sync::Arc};
+
+/// Phase function of participating media scattering light equally in all directions.
+pub struct Isotropic<'a> {
+    albedo: Arc<dyn Texture + 'a + Send + Sync>
+}
+
+impl<'a> Isotropic<'a> {
+    /// Creates new `Isotropic` phase function. Albedo is fraction of light scattered (not absorbed) by particles.
+    pub fn new(albedo: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            albedo: Arc::new(albedo)
+        }
+    }
+
+    fn albedo(&self, hit_record: &HitRecord) -> RGB {
+        let (u, v) = hit_record.uv();
+        self.albedo.value(u, v, hit_record.point())
+    }
+}
+
+impl<'a> Scatter for Isotropic<'a> {
+    fn scatter(&self, _: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        Some((Ray::new(hit_record.point(), Vec3::random_unit_vector()), self.albedo(hit_record)))
+    }
+
+    fn evaluate(&self, _: &Ray, hit_record: &HitRecord, _: Vec3<f64>) -> Option<(RGB, f64)> {
+        let phase = 0.25 / PI;
+        Some((self.albedo(hit_record) * phase, phase))
+    }
+}
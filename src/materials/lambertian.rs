@@ -1,21 +1,28 @@
-use crate::{hit::HitRecord, rgb::RGB, scatter::Scatter, math::{Vec3, Ray}};
+use crate::{hit::HitRecord, rgb::RGB, scatter::Scatter, texture::Texture, math::{Vec3, Ray}};
+
+use std::{f64::consts::FRAC_1_PI, sync::Arc};
 
 /// A simple diffuse material.\
 /// When rays intersects object it bounces from the surface in random direction.
-pub struct Lambertian {
-    albedo: RGB
+pub struct Lambertian<'a> {
+    albedo: Arc<dyn Texture + 'a + Send + Sync>
 }
 
-impl Lambertian {
-    /// Creates new Lambertian material.
-    pub fn new(albedo: RGB) -> Self {
+impl<'a> Lambertian<'a> {
+    /// Creates new Lambertian material. Albedo is any `Texture`, e.g. constant `RGB`.
+    pub fn new(albedo: impl Texture + 'a + Send + Sync) -> Self {
         Self {
-            albedo
+            albedo: Arc::new(albedo)
         }
     }
+
+    fn albedo(&self, hit_record: &HitRecord) -> RGB {
+        let (u, v) = hit_record.uv();
+        self.albedo.value(u, v, hit_record.point())
+    }
 }
 
-impl Scatter for Lambertian {
+impl<'a> Scatter for Lambertian<'a> {
     fn scatter(&self, _: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
         let normal = hit_record.normal();
 
@@ -25,6 +32,11 @@ impl Scatter for Lambertian {
         }
 
         let scattered_ray = Ray::new(hit_record.point(), scatter_direction);
-        Some((scattered_ray, self.albedo))
+        Some((scattered_ray, self.albedo(hit_record)))
+    }
+
+    fn evaluate(&self, _: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let cosine = hit_record.normal().dot(&direction.normalize()).max(0.0);
+        Some((self.albedo(hit_record) * (cosine * FRAC_1_PI), cosine * FRAC_1_PI))
     }
 }
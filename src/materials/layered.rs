@@ -0,0 +1,76 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, random::random_in_range, rgb::RGB, scatter::Scatter};
+
+use super::microfacet::fresnel_dielectric;
+
+use std::sync::Arc;
+
+/// Clear coat over `base` material, e.g. varnished wood or car paint.\
+/// Light is reflected by `coat` with probability given by Fresnel reflectance of dielectric with refraction index `ior`,
+/// otherwise it passes to the base and back. Coat material only shapes reflection, so it should be white,
+/// like `Metal::new(RGB(1.0, 1.0, 1.0), 0.05)`.
+/// ```
+/// use rayimg::{materials::{Lambertian, Layered, Metal}, RGB};
+///
+/// let varnished_wood = Layered::new(Metal::new(RGB(1.0, 1.0, 1.0), 0.05), Lambertian::new(RGB(0.4, 0.2, 0.1))).ior(1.5);
+/// ```
+pub struct Layered<'a> {
+    coat: Arc<dyn Scatter + 'a + Send + Sync>,
+    base: Arc<dyn Scatter + 'a + Send + Sync>,
+    ior: f64
+}
+
+impl<'a> Layered<'a> {
+    /// Creates new `Layered` material with coat refraction index 1.5.
+    pub fn new(coat: impl Scatter + 'a + Send + Sync, base: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            coat: Arc::new(coat),
+            base: Arc::new(base),
+            ior: 1.5
+        }
+    }
+
+    /// Sets refraction index of the coat.
+    pub fn ior(mut self, ior: f64) -> Self {
+        self.ior = ior;
+        self
+    }
+
+    /// Returns Fresnel reflectance of the coat for light travelling along `direction`, or away from surface in reversed `direction`.
+    fn reflectance(&self, hit_record: &HitRecord, direction: Vec3<f64>) -> f64 {
+        fresnel_dielectric(hit_record.normal().dot(&direction.normalize()).abs(), self.ior)
+    }
+}
+
+impl<'a> Scatter for Layered<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        if random_in_range(0.0..1.0) < self.reflectance(hit_record, ray.direction()) {
+            return self.coat.scatter(ray, hit_record);
+        }
+
+        let (scattered, color) = self.base.scatter(ray, hit_record)?;
+        let transmittance = 1.0 - self.reflectance(hit_record, scattered.direction());
+        Some((scattered, color * transmittance))
+    }
+
+    fn emit(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        self.base.emit(ray, hit_record) * (1.0 - self.reflectance(hit_record, ray.direction()))
+    }
+
+    fn emission(&self) -> RGB {
+        self.base.emission()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.coat.is_dispersive() || self.base.is_dispersive()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let (coat_value, coat_pdf) = self.coat.evaluate(ray, hit_record, direction)?;
+        let (base_value, base_pdf) = self.base.evaluate(ray, hit_record, direction)?;
+
+        let reflectance = self.reflectance(hit_record, ray.direction());
+        let transmittance = (1.0 - reflectance) * (1.0 - self.reflectance(hit_record, direction));
+
+        Some((coat_value * reflectance + base_value * transmittance, coat_pdf * reflectance + base_pdf * (1.0 - reflectance)))
+    }
+}
@@ -0,0 +1,225 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3, ONB}, random::random_in_range, rgb::RGB, scatter::Scatter};
+
+use std::{f64::consts::{FRAC_PI_2, PI}, fs::File, io::{self, BufReader, Read}, path::Path};
+
+/// Isotropic BRDF measured from real material, tabulated in half and difference angles like the MERL database.\
+/// Table is indexed by `theta_half` (with square root spacing), `theta_diff` and `phi_diff`. Directions are sampled
+/// from tabulated distribution of reflected light mixed with cosine sampling, so glossy materials converge too.
+/// ```
+/// use rayimg::{materials::MeasuredBRDF, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+///
+/// let lambertian = MeasuredBRDF::new((4, 4, 8), vec![RGB(0.5, 0.5, 0.5) / std::f64::consts::PI; 4 * 4 * 8]);
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+/// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+/// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+///
+/// let (value, _) = lambertian.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, 1.0)).unwrap();
+/// assert!((value.g() - 0.5 / std::f64::consts::PI).abs() < 1e-9);
+/// ```
+#[derive(Debug, Clone)]
+pub struct MeasuredBRDF {
+    resolution: (usize, usize, usize),
+    values: Vec<RGB>,
+    cdfs: Vec<f64>
+}
+
+impl MeasuredBRDF {
+    const THETA_BINS: usize = 16;
+    const COSINE_BINS: usize = 32;
+    const PHI_BINS: usize = 64;
+    const COSINE_PROBABILITY: f64 = 0.25;
+    const SCALE: RGB = RGB(1.0 / 1500.0, 1.15 / 1500.0, 1.66 / 1500.0);
+
+    /// Creates new `MeasuredBRDF` from values with resolution of `theta_half`, `theta_diff` and `phi_diff`,
+    /// ordered with `phi_diff` changing fastest. `phi_diff` covers `0.0..PI` thanks to reciprocity.
+    pub fn new(resolution: (usize, usize, usize), values: Vec<RGB>) -> Self {
+        assert!(resolution.0 > 0 && resolution.1 > 0 && resolution.2 > 0, "BRDF table must not be empty");
+        assert_eq!(values.len(), resolution.0 * resolution.1 * resolution.2, "Value count does not match BRDF table resolution");
+
+        let mut brdf = Self {
+            resolution,
+            values: values.into_iter().map(|value| RGB(value.0.max(0.0), value.1.max(0.0), value.2.max(0.0))).collect(),
+            cdfs: Vec::new()
+        };
+
+        brdf.cdfs = brdf.build_distributions();
+        brdf
+    }
+
+    /// Loads `MeasuredBRDF` from MERL `.binary` file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads `MeasuredBRDF` in MERL `.binary` format: three 32-bit resolutions followed by red, green
+    /// and blue tables of little-endian 64-bit floats. Negative values mark missing measurements.
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let invalid_data = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut dimensions = [0; 3];
+        for dimension in &mut dimensions {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            *dimension = i32::from_le_bytes(bytes);
+        }
+
+        if dimensions.iter().any(|&dimension| dimension <= 0) {
+            return Err(invalid_data("invalid BRDF table dimension"));
+        }
+
+        let resolution = (dimensions[0] as usize, dimensions[1] as usize, dimensions[2] as usize);
+        let count = resolution.0.checked_mul(resolution.1).and_then(|count| count.checked_mul(resolution.2))
+            .ok_or_else(|| invalid_data("BRDF table is too large"))?;
+        let byte_count = count.checked_mul(8).ok_or_else(|| invalid_data("BRDF table is too large"))?;
+
+        let mut values = Vec::new();
+        for channel in 0..3 {
+            // Buffer grows with data actually read, so a corrupt header can't request huge allocation up front.
+            let mut bytes = Vec::new();
+            (&mut reader).take(byte_count as u64).read_to_end(&mut bytes)?;
+            if bytes.len() != byte_count {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "BRDF table is shorter than its header"));
+            }
+            if channel == 0 {
+                values = vec![RGB::default(); count];
+            }
+
+            for (value, bytes) in values.iter_mut().zip(bytes.chunks_exact(8)) {
+                let sample = f64::from_le_bytes(bytes.try_into().unwrap());
+                match channel {
+                    0 => value.0 = sample * Self::SCALE.0,
+                    1 => value.1 = sample * Self::SCALE.1,
+                    _ => value.2 = sample * Self::SCALE.2
+                }
+            }
+        }
+
+        Ok(Self::new(resolution, values))
+    }
+
+    /// Returns resolution of `theta_half`, `theta_diff` and `phi_diff`.
+    pub fn resolution(&self) -> (usize, usize, usize) {
+        self.resolution
+    }
+
+    /// Returns BRDF for directions in local space with normal along `z`.
+    fn brdf(&self, wi: Vec3<f64>, wo: Vec3<f64>) -> RGB {
+        let half = (wi + wo).normalize();
+        let (theta_half, phi_half) = (half.z.clamp(-1.0, 1.0).acos(), half.y.atan2(half.x));
+
+        // Rotate incident direction into frame where half vector is the north pole.
+        let (sin, cos) = (-phi_half).sin_cos();
+        let rotated = Vec3::new(wi.x * cos - wi.y * sin, wi.x * sin + wi.y * cos, wi.z);
+        let (sin, cos) = (-theta_half).sin_cos();
+        let diff = Vec3::new(rotated.x * cos + rotated.z * sin, rotated.y, rotated.z * cos - rotated.x * sin);
+
+        let theta_diff = diff.z.clamp(-1.0, 1.0).acos();
+        let mut phi_diff = diff.y.atan2(diff.x);
+        if phi_diff < 0.0 {
+            phi_diff += PI;
+        }
+
+        let index = |position: f64, resolution: usize| ((position * resolution as f64) as usize).min(resolution - 1);
+        let theta_half_index = index((theta_half / FRAC_PI_2).max(0.0).sqrt(), self.resolution.0);
+        let theta_diff_index = index(theta_diff / FRAC_PI_2, self.resolution.1);
+        let phi_diff_index = index(phi_diff / PI, self.resolution.2);
+
+        self.values[(theta_half_index * self.resolution.1 + theta_diff_index) * self.resolution.2 + phi_diff_index]
+    }
+
+    /// Builds distributions of reflected light over cells of cosine and azimuth relative to outgoing direction
+    /// for every bin of outgoing angle. Cells have equal solid angle.
+    fn build_distributions(&self) -> Vec<f64> {
+        let cells = Self::COSINE_BINS * Self::PHI_BINS;
+        let mut cdfs = vec![0.0; Self::THETA_BINS * (cells + 1)];
+
+        for theta_bin in 0..Self::THETA_BINS {
+            let theta = (theta_bin as f64 + 0.5) / Self::THETA_BINS as f64 * FRAC_PI_2;
+            let wo = Vec3::new(theta.sin(), 0.0, theta.cos());
+
+            let cdf = &mut cdfs[theta_bin * (cells + 1)..(theta_bin + 1) * (cells + 1)];
+            for cell in 0..cells {
+                let cosine = ((cell / Self::PHI_BINS) as f64 + 0.5) / Self::COSINE_BINS as f64;
+                let phi = ((cell % Self::PHI_BINS) as f64 + 0.5) / Self::PHI_BINS as f64 * 2.0 * PI;
+                let sine = (1.0 - cosine * cosine).sqrt();
+
+                let wi = Vec3::new(sine * phi.cos(), sine * phi.sin(), cosine);
+                cdf[cell + 1] = cdf[cell] + self.brdf(wi, wo).luminance().max(0.0) * cosine;
+            }
+
+            let total = cdf[cells];
+            for (cell, value) in cdf.iter_mut().enumerate() {
+                *value = if total > 0.0 { *value / total } else { cell as f64 / cells as f64 };
+            }
+        }
+
+        cdfs
+    }
+
+    fn cdf(&self, wo: Vec3<f64>) -> &[f64] {
+        let theta_bin = ((wo.z.clamp(0.0, 1.0).acos() / FRAC_PI_2 * Self::THETA_BINS as f64) as usize).min(Self::THETA_BINS - 1);
+        let cells = Self::COSINE_BINS * Self::PHI_BINS;
+        &self.cdfs[theta_bin * (cells + 1)..(theta_bin + 1) * (cells + 1)]
+    }
+
+    /// Returns probability density of sampling `wi` given `wo`, both in local space.
+    fn pdf(&self, wo: Vec3<f64>, wi: Vec3<f64>) -> f64 {
+        let cosine_bin = ((wi.z * Self::COSINE_BINS as f64) as usize).min(Self::COSINE_BINS - 1);
+        let phi = (wi.y.atan2(wi.x) - wo.y.atan2(wo.x)).rem_euclid(2.0 * PI);
+        let phi_bin = ((phi / (2.0 * PI) * Self::PHI_BINS as f64) as usize).min(Self::PHI_BINS - 1);
+
+        let cdf = self.cdf(wo);
+        let cell = cosine_bin * Self::PHI_BINS + phi_bin;
+        let cell_solid_angle = 2.0 * PI / (Self::COSINE_BINS * Self::PHI_BINS) as f64;
+
+        Self::COSINE_PROBABILITY * wi.z / PI + (1.0 - Self::COSINE_PROBABILITY) * (cdf[cell + 1] - cdf[cell]) / cell_solid_angle
+    }
+
+    fn sample(&self, wo: Vec3<f64>) -> Vec3<f64> {
+        if random_in_range(0.0..1.0) < Self::COSINE_PROBABILITY {
+            return Vec3::random_cosine_direction();
+        }
+
+        let cdf = self.cdf(wo);
+        let value = random_in_range(0.0..1.0);
+        let mut cell = cdf.partition_point(|&c| c <= value).saturating_sub(1).min(cdf.len() - 2);
+        // Skip zero-probability cells which share their bounds with a neighbour.
+        while cell < cdf.len() - 2 && cdf[cell + 1] <= cdf[cell] {
+            cell += 1;
+        }
+
+        let cosine = ((cell / Self::PHI_BINS) as f64 + random_in_range(0.0..1.0)) / Self::COSINE_BINS as f64;
+        let phi = ((cell % Self::PHI_BINS) as f64 + random_in_range(0.0..1.0)) / Self::PHI_BINS as f64 * 2.0 * PI + wo.y.atan2(wo.x);
+        let sine = (1.0 - cosine * cosine).max(0.0).sqrt();
+
+        Vec3::new(sine * phi.cos(), sine * phi.sin(), cosine)
+    }
+}
+
+impl Scatter for MeasuredBRDF {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let onb = ONB::new(hit_record.normal());
+        let wo = onb.project(-ray.direction().normalize());
+        if wo.z <= 0.0 {
+            return None;
+        }
+
+        let wi = self.sample(wo);
+        let pdf = self.pdf(wo, wi);
+        if wi.z <= 0.0 || pdf <= 0.0 {
+            return None;
+        }
+
+        Some((Ray::new(hit_record.point(), onb.local(wi)), self.brdf(wi, wo) * (wi.z / pdf)))
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let onb = ONB::new(hit_record.normal());
+        let (wo, wi) = (onb.project(-ray.direction().normalize()), onb.project(direction.normalize()));
+        if wo.z <= 0.0 || wi.z <= 0.0 {
+            return Some((RGB::default(), 0.0));
+        }
+
+        Some((self.brdf(wi, wo) * wi.z, self.pdf(wo, wi)))
+    }
+}
@@ -1,30 +1,64 @@
-use crate::{rgb::RGB, scatter::Scatter, math::{Vec3, Ray}, hit::HitRecord};
+use crate::{rgb::RGB, scatter::Scatter, texture::Texture, math::{Vec3, Ray}, hit::HitRecord};
+
+use super::thin_film::{Substrate, ThinFilm};
+
+use std::sync::Arc;
 
 /// Material that reflects incident rays.
-pub struct Metal {
-    albedo: RGB,
-    fuzziness: f64
+pub struct Metal<'a> {
+    albedo: Arc<dyn Texture + 'a + Send + Sync>,
+    fuzziness: Arc<dyn Texture + 'a + Send + Sync>,
+    thin_film: Option<ThinFilm>
 }
 
-impl Metal {
-    /// Creates new Metal material.
-    pub fn new(albedo: RGB, fuzziness: f64) -> Self {
+impl<'a> Metal<'a> {
+    /// Creates new Metal material. Both albedo and fuzziness are `Texture`s, fuzziness is luminance of its value clamped to `0.0..=1.0`.
+    pub fn new(albedo: impl Texture + 'a + Send + Sync, fuzziness: impl Texture + 'a + Send + Sync) -> Self {
         Self {
-            albedo,
-            fuzziness: fuzziness.clamp(0.0, 1.0)
+            albedo: Arc::new(albedo),
+            fuzziness: Arc::new(fuzziness),
+            thin_film: None
         }
     }
+
+    /// Coats the metal with `ThinFilm`, e.g. oxide layer of anodized or heated metal.
+    /// ```
+    /// use rayimg::{materials::{Metal, ThinFilm}, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+    ///
+    /// let anodized = Metal::new(RGB(0.9, 0.9, 0.9), 0.0).thin_film(ThinFilm::new(300.0, 1.6));
+    /// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    /// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+    /// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+    ///
+    /// let (_, color) = anodized.scatter(&ray, &hit_record).unwrap();
+    /// assert!(color.r() != color.g() || color.g() != color.b());
+    /// ```
+    pub fn thin_film(mut self, thin_film: ThinFilm) -> Self {
+        self.thin_film = Some(thin_film);
+        self
+    }
 }
 
-impl Scatter for Metal {
+impl<'a> Scatter for Metal<'a> {
     fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
         let normal = hit_record.normal();
+        let ((u, v), point) = (hit_record.uv(), hit_record.point());
+        let fuzziness = self.fuzziness.value(u, v, point).luminance().clamp(0.0, 1.0);
 
         let reflected = ray.direction().normalize().reflect(normal);
         if reflected.dot(&normal) <= 0.0 {
             None
         } else {
-            Some((Ray::new(hit_record.point(), reflected + Vec3::random_in_unit_sphere() * self.fuzziness), self.albedo))
+            let albedo = self.albedo.value(u, v, point);
+            let color = match &self.thin_film {
+                Some(thin_film) => thin_film.reflectance(reflected.dot(&normal), &Substrate::Mirror(albedo), ray.wavelength()),
+                None => albedo
+            };
+            Some((Ray::new(point, reflected + Vec3::random_in_unit_sphere() * fuzziness), color))
         }
     }
+
+    fn is_dispersive(&self) -> bool {
+        self.thin_film.is_some()
+    }
 }
@@ -0,0 +1,138 @@
+use crate::{math::Vec3, random::random_in_range, rgb::RGB};
+
+use std::f64::consts::PI;
+
+/// Isotropic GGX (Trowbridge-Reitz) distribution of microfacet normals.\
+/// All directions are in local coordinates with macro surface normal along `Z`.
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct TrowbridgeReitz {
+    alpha: f64
+}
+
+impl TrowbridgeReitz {
+    /// Roughness below this threshold is treated as perfectly smooth surface.
+    const SMOOTH_ALPHA: f64 = 1e-3;
+
+    /// Creates distribution from perceptual `roughness` in `0.0..=1.0`, `alpha = roughness²`.
+    pub fn from_roughness(roughness: f64) -> Self {
+        let roughness = roughness.clamp(0.0, 1.0);
+        Self {
+            alpha: roughness * roughness
+        }
+    }
+
+    pub fn alpha(&self) -> f64 {
+        self.alpha
+    }
+
+    pub fn is_smooth(&self) -> bool {
+        self.alpha < Self::SMOOTH_ALPHA
+    }
+
+    /// Density of microfacet normal `h`.
+    pub fn d(&self, h: Vec3<f64>) -> f64 {
+        if h.z <= 0.0 {
+            return 0.0;
+        }
+
+        let alpha2 = self.alpha * self.alpha;
+        let denominator = h.z * h.z * (alpha2 - 1.0) + 1.0;
+        alpha2 / (PI * denominator * denominator)
+    }
+
+    fn lambda(&self, w: Vec3<f64>) -> f64 {
+        let cos2 = w.z * w.z;
+        if cos2 <= 0.0 {
+            return f64::INFINITY;
+        }
+
+        let tan2 = (1.0 - cos2).max(0.0) / cos2;
+        0.5 * ((1.0 + self.alpha * self.alpha * tan2).sqrt() - 1.0)
+    }
+
+    /// Smith masking of direction `w`.
+    pub fn g1(&self, w: Vec3<f64>) -> f64 {
+        1.0 / (1.0 + self.lambda(w))
+    }
+
+    /// Height-correlated Smith masking and shadowing of directions `wo` and `wi`.
+    pub fn g2(&self, wo: Vec3<f64>, wi: Vec3<f64>) -> f64 {
+        1.0 / (1.0 + self.lambda(wo) + self.lambda(wi))
+    }
+
+    /// Density of `sample_visible_normal` choosing `h` as seen from `wo`.
+    pub fn visible_normal_pdf(&self, wo: Vec3<f64>, h: Vec3<f64>) -> f64 {
+        if wo.z <= 0.0 {
+            return 0.0;
+        }
+
+        self.g1(wo) * wo.dot(&h).max(0.0) * self.d(h) / wo.z
+    }
+
+    /// Samples microfacet normal visible from `wo` (Heitz, "Sampling the TrowbridgeReitz Distribution of Visible Normals").
+    pub fn sample_visible_normal(&self, wo: Vec3<f64>) -> Vec3<f64> {
+        let stretched = Vec3::new(self.alpha * wo.x, self.alpha * wo.y, wo.z).normalize();
+
+        let length_squared = stretched.x * stretched.x + stretched.y * stretched.y;
+        let t1 = if length_squared > 0.0 {
+            Vec3::new(-stretched.y, stretched.x, 0.0) / length_squared.sqrt()
+        } else {
+            Vec3::new(1.0, 0.0, 0.0)
+        };
+        let t2 = stretched.cross(&t1);
+
+        let r = random_in_range(0.0..1.0f64).sqrt();
+        let phi = 2.0 * PI * random_in_range(0.0..1.0);
+        let (p1, p2) = (r * phi.cos(), r * phi.sin());
+        let s = 0.5 * (1.0 + stretched.z);
+        let p2 = (1.0 - s) * (1.0 - p1 * p1).sqrt() + s * p2;
+
+        let h = t1 * p1 + t2 * p2 + stretched * (1.0 - p1 * p1 - p2 * p2).max(0.0).sqrt();
+        Vec3::new(self.alpha * h.x, self.alpha * h.y, h.z.max(1e-6)).normalize()
+    }
+}
+
+/// Unpolarized Fresnel reflectance of dielectric interface with relative index of refraction `eta` (transmitted / incident side).
+pub(crate) fn fresnel_dielectric(cos_i: f64, eta: f64) -> f64 {
+    let cos_i = cos_i.clamp(-1.0, 1.0);
+    let (cos_i, eta) = if cos_i < 0.0 { (-cos_i, 1.0 / eta) } else { (cos_i, eta) };
+
+    let sin2_t = (1.0 - cos_i * cos_i) / (eta * eta);
+    if sin2_t >= 1.0 {
+        return 1.0;
+    }
+
+    let cos_t = (1.0 - sin2_t).sqrt();
+    let parallel = (eta * cos_i - cos_t) / (eta * cos_i + cos_t);
+    let perpendicular = (cos_i - eta * cos_t) / (cos_i + eta * cos_t);
+    0.5 * (parallel * parallel + perpendicular * perpendicular)
+}
+
+/// Fresnel reflectance of conductor with complex index of refraction `eta + i * k`, per color channel.
+pub(crate) fn fresnel_conductor(cos_i: f64, eta: RGB, k: RGB) -> RGB {
+    let channel = |eta: f64, k: f64| {
+        let cos2 = cos_i.clamp(0.0, 1.0).powi(2);
+        let sin2 = 1.0 - cos2;
+
+        let t0 = eta * eta - k * k - sin2;
+        let a2b2 = (t0 * t0 + 4.0 * eta * eta * k * k).sqrt();
+        let a = (0.5 * (a2b2 + t0)).max(0.0).sqrt();
+
+        let t1 = a2b2 + cos2;
+        let t2 = 2.0 * cos2.sqrt() * a;
+        let perpendicular = (t1 - t2) / (t1 + t2);
+
+        let t3 = cos2 * a2b2 + sin2 * sin2;
+        let t4 = t2 * sin2;
+        let parallel = perpendicular * (t3 - t4) / (t3 + t4);
+
+        0.5 * (parallel + perpendicular)
+    };
+
+    RGB(channel(eta.0, k.0), channel(eta.1, k.1), channel(eta.2, k.2))
+}
+
+/// Schlick approximation of Fresnel reflectance with reflectance `f0` at normal incidence.
+pub(crate) fn fresnel_schlick(cos_i: f64, f0: RGB) -> RGB {
+    f0 + (RGB(1.0, 1.0, 1.0) - f0) * (1.0 - cos_i.clamp(0.0, 1.0)).powi(5)
+}
@@ -0,0 +1,72 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, random::random_in_range, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use std::sync::Arc;
+
+/// Blends two materials, e.g. metal partially covered with paint.\
+/// `amount` is fraction of the `second` material, luminance of the texture clamped to `0.0..=1.0`.\
+/// Power of mixed lights uses `amount` averaged over texture coordinates, with textures varying in space taken at the origin.
+/// ```
+/// use rayimg::{materials::{Lambertian, Metal, Mix}, RGB};
+///
+/// let scratched_paint = Mix::new(Lambertian::new(RGB(0.8, 0.1, 0.1)), Metal::new(RGB(0.9, 0.9, 0.9), 0.2), 0.7);
+/// ```
+pub struct Mix<'a> {
+    first: Arc<dyn Scatter + 'a + Send + Sync>,
+    second: Arc<dyn Scatter + 'a + Send + Sync>,
+    amount: Arc<dyn Texture + 'a + Send + Sync>,
+    average_amount: f64
+}
+
+impl<'a> Mix<'a> {
+    /// Creates new `Mix` material.
+    pub fn new(first: impl Scatter + 'a + Send + Sync, second: impl Scatter + 'a + Send + Sync, amount: impl Texture + 'a + Send + Sync) -> Self {
+        const GRID: usize = 16;
+        let average_amount = (0..GRID * GRID).map(|index| {
+            let (u, v) = (((index % GRID) as f64 + 0.5) / GRID as f64, ((index / GRID) as f64 + 0.5) / GRID as f64);
+            amount.value(u, v, Vec3::new(0.0, 0.0, 0.0)).luminance().clamp(0.0, 1.0)
+        }).sum::<f64>() / (GRID * GRID) as f64;
+
+        Self {
+            first: Arc::new(first),
+            second: Arc::new(second),
+            amount: Arc::new(amount),
+            average_amount
+        }
+    }
+
+    fn amount(&self, hit_record: &HitRecord) -> f64 {
+        let (u, v) = hit_record.uv();
+        self.amount.value(u, v, hit_record.point()).luminance().clamp(0.0, 1.0)
+    }
+}
+
+impl<'a> Scatter for Mix<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        if random_in_range(0.0..1.0) < self.amount(hit_record) {
+            self.second.scatter(ray, hit_record)
+        } else {
+            self.first.scatter(ray, hit_record)
+        }
+    }
+
+    fn emit(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        let amount = self.amount(hit_record);
+        self.first.emit(ray, hit_record) * (1.0 - amount) + self.second.emit(ray, hit_record) * amount
+    }
+
+    fn emission(&self) -> RGB {
+        self.first.emission() * (1.0 - self.average_amount) + self.second.emission() * self.average_amount
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.first.is_dispersive() || self.second.is_dispersive()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let amount = self.amount(hit_record);
+        let (first_value, first_pdf) = self.first.evaluate(ray, hit_record, direction)?;
+        let (second_value, second_pdf) = self.second.evaluate(ray, hit_record, direction)?;
+
+        Some((first_value * (1.0 - amount) + second_value * amount, first_pdf * (1.0 - amount) + second_pdf * amount))
+    }
+}
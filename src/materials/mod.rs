@@ -1,5 +1,19 @@
 mod lambertian;
 mod metal;
 mod dielectric;
+mod diffuse_light;
+mod normal_map;
+mod bump_map;
+mod microfacet;
+mod conductor;
+mod principled;
+mod isotropic;
+mod henyey_greenstein;
+mod mix;
+mod layered;
+mod two_sided;
+mod subsurface;
+mod thin_film;
+mod measured_brdf;
 
-pub use {lambertian::Lambertian, metal::Metal, dielectric::Dielectric};
+pub use {lambertian::Lambertian, metal::Metal, dielectric::{Dielectric, Dispersion}, diffuse_light::DiffuseLight, normal_map::NormalMap, bump_map::BumpMap, conductor::Conductor, principled::Principled, isotropic::Isotropic, henyey_greenstein::HenyeyGreenstein, mix::Mix, layered::Layered, two_sided::TwoSided, subsurface::Subsurface, thin_film::ThinFilm, measured_brdf::MeasuredBRDF};
@@ -0,0 +1,90 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use std::sync::Arc;
+
+/// Wraps any material and replaces its shading normal with one read from tangent-space normal map.\
+/// Red, green and blue channels in `0.0..=1.0` encode coordinates along `dpdu`, `dpdv` and surface normal,
+/// so flat map has color `RGB(0.5, 0.5, 1.0)`. Normal maps should be loaded with `ImageTexture::load_linear`.
+/// ```
+/// use rayimg::{materials::{Lambertian, NormalMap}, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+///
+/// let material = NormalMap::new(Lambertian::new(RGB(1.0, 1.0, 1.0)), RGB(1.0, 0.5, 0.5));
+/// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+/// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+/// hit_record.set_tangents(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+///
+/// // Normal is tilted all the way towards `dpdu`, so light coming along it is reflected the most.
+/// let (tilted, _) = material.evaluate(&ray, &hit_record, Vec3::new(1.0, 0.0, 0.0)).unwrap();
+/// let (straight, _) = material.evaluate(&ray, &hit_record, Vec3::new(0.0, 0.0, 1.0)).unwrap();
+/// assert!(tilted.r() > straight.r());
+/// ```
+pub struct NormalMap<'a> {
+    material: Arc<dyn Scatter + 'a + Send + Sync>,
+    normals: Arc<dyn Texture + 'a + Send + Sync>,
+    strength: f64
+}
+
+impl<'a> NormalMap<'a> {
+    /// Creates new `NormalMap` over `material`.
+    pub fn new(material: impl Scatter + 'a + Send + Sync, normals: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            material: Arc::new(material),
+            normals: Arc::new(normals),
+            strength: 1.0
+        }
+    }
+
+    /// Scales tangent part of mapped normals. Default is 1.0, 0.0 disables the map.
+    pub fn strength(mut self, strength: f64) -> Self {
+        self.strength = strength;
+        self
+    }
+
+    fn perturb<'b>(&self, hit_record: &HitRecord<'b>) -> HitRecord<'b> {
+        let outward = if hit_record.front_face() { hit_record.normal() } else { -hit_record.normal() };
+        let (dpdu, dpdv) = hit_record.tangents();
+
+        // Gram-Schmidt, so the frame stays orthonormal for interpolated shading normals.
+        let tangent = dpdu - outward * outward.dot(&dpdu);
+        if tangent.near_epsilon(1e-12) {
+            return *hit_record;
+        }
+
+        let tangent = tangent.normalize();
+        let mut bitangent = outward.cross(&tangent);
+        if bitangent.dot(&dpdv) < 0.0 {
+            bitangent = -bitangent;
+        }
+
+        let (u, v) = hit_record.uv();
+        let color = self.normals.value(u, v, hit_record.point());
+        let local = Vec3::new((2.0 * color.r() - 1.0) * self.strength, (2.0 * color.g() - 1.0) * self.strength, (2.0 * color.b() - 1.0).max(1e-3));
+
+        let mut perturbed = *hit_record;
+        perturbed.set_shading_normal((tangent * local.x + bitangent * local.y + outward * local.z).normalize());
+        perturbed
+    }
+}
+
+impl<'a> Scatter for NormalMap<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        self.material.scatter(ray, &self.perturb(hit_record))
+    }
+
+    fn emit(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        self.material.emit(ray, hit_record)
+    }
+
+    fn emission(&self) -> RGB {
+        self.material.emission()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.material.is_dispersive()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        self.material.evaluate(ray, &self.perturb(hit_record), direction)
+    }
+}
@@ -0,0 +1,266 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3, ONB}, random::random_in_range, rgb::RGB, scatter::Scatter, texture::Texture};
+
+use super::microfacet::{fresnel_dielectric, fresnel_schlick, TrowbridgeReitz};
+
+use std::{f64::consts::FRAC_1_PI, sync::Arc};
+
+/// Physically based "uber" material in the spirit of Disney principled BRDF.\
+/// Combines diffuse, sheen, specular, clearcoat and transmission lobes controlled by sliders in `0.0..=1.0`,
+/// so one material covers plastics, metals, fabrics, glass and car paint. Directions are importance sampled
+/// by choosing lobes proportionally to their weights.
+/// ```
+/// use rayimg::{materials::Principled, RGB};
+///
+/// let car_paint = Principled::new(RGB(0.6, 0.05, 0.05)).roughness(0.4).clearcoat(1.0).clearcoat_roughness(0.05);
+/// let brushed_gold = Principled::new(RGB(1.0, 0.78, 0.34)).metallic(1.0).roughness(0.35);
+/// let frosted_glass = Principled::new(RGB(1.0, 1.0, 1.0)).transmission(1.0).roughness(0.2);
+/// ```
+pub struct Principled<'a> {
+    base_color: Arc<dyn Texture + 'a + Send + Sync>,
+    metallic: Arc<dyn Texture + 'a + Send + Sync>,
+    roughness: Arc<dyn Texture + 'a + Send + Sync>,
+    specular: f64,
+    sheen: f64,
+    sheen_tint: f64,
+    clearcoat: f64,
+    clearcoat_roughness: f64,
+    transmission: f64,
+    ior: f64,
+    emission: RGB
+}
+
+/// Parameters of `Principled` evaluated at hit point.
+struct Lobes {
+    base_color: RGB,
+    metallic: f64,
+    specular: TrowbridgeReitz,
+    clearcoat: TrowbridgeReitz,
+    eta: f64,
+    weights: [f64; 4]
+}
+
+impl<'a> Principled<'a> {
+    const MIN_ROUGHNESS: f64 = 0.05;
+
+    /// Creates new `Principled` material with given base color, dielectric, roughness 0.5 and specular 0.5.
+    pub fn new(base_color: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            base_color: Arc::new(base_color),
+            metallic: Arc::new(0.0),
+            roughness: Arc::new(0.5),
+            specular: 0.5,
+            sheen: 0.0,
+            sheen_tint: 0.5,
+            clearcoat: 0.0,
+            clearcoat_roughness: 0.1,
+            transmission: 0.0,
+            ior: 1.5,
+            emission: RGB::default()
+        }
+    }
+
+    /// Sets blend between dielectric (0.0) and metal (1.0), which reflects tinted by base color.
+    pub fn metallic(mut self, metallic: impl Texture + 'a + Send + Sync) -> Self {
+        self.metallic = Arc::new(metallic);
+        self
+    }
+
+    /// Sets microfacet roughness of specular and transmission lobes.\
+    /// Values below 0.05 are raised to 0.05, so `roughness(0.0)` gives sharp gloss rather than a perfect mirror.
+    pub fn roughness(mut self, roughness: impl Texture + 'a + Send + Sync) -> Self {
+        self.roughness = Arc::new(roughness);
+        self
+    }
+
+    /// Sets strength of dielectric specular reflection, 0.5 is reflectance of 4%.
+    pub fn specular(mut self, specular: f64) -> Self {
+        self.specular = specular.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets strength of retroreflective grazing sheen of cloth.
+    pub fn sheen(mut self, sheen: f64) -> Self {
+        self.sheen = sheen.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets blend of sheen color between white and base color.
+    pub fn sheen_tint(mut self, sheen_tint: f64) -> Self {
+        self.sheen_tint = sheen_tint.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets strength of white clear lacquer layer on top of the surface.
+    pub fn clearcoat(mut self, clearcoat: f64) -> Self {
+        self.clearcoat = clearcoat.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets roughness of clearcoat layer.\
+    /// Like `roughness`, values below 0.05 are raised to 0.05.
+    pub fn clearcoat_roughness(mut self, clearcoat_roughness: f64) -> Self {
+        self.clearcoat_roughness = clearcoat_roughness.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets fraction of light refracted through the surface instead of diffusely reflected.
+    pub fn transmission(mut self, transmission: f64) -> Self {
+        self.transmission = transmission.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets index of refraction used by transmission. Default is 1.5.
+    pub fn ior(mut self, ior: f64) -> Self {
+        self.ior = ior;
+        self
+    }
+
+    /// Sets radiance emitted from the front face.
+    pub fn emission(mut self, emission: RGB) -> Self {
+        self.emission = emission;
+        self
+    }
+
+    fn lobes(&self, hit_record: &HitRecord) -> Lobes {
+        let ((u, v), point) = (hit_record.uv(), hit_record.point());
+        let base_color = self.base_color.value(u, v, point);
+        let metallic = self.metallic.value(u, v, point).luminance().clamp(0.0, 1.0);
+        let roughness = self.roughness.value(u, v, point).luminance().max(Self::MIN_ROUGHNESS);
+
+        let dielectric = 1.0 - metallic;
+        let weights = [
+            dielectric * (1.0 - self.transmission),
+            1.0,
+            0.25 * self.clearcoat,
+            dielectric * self.transmission
+        ];
+        let total = weights.iter().sum::<f64>();
+
+        Lobes {
+            base_color,
+            metallic,
+            specular: TrowbridgeReitz::from_roughness(roughness),
+            clearcoat: TrowbridgeReitz::from_roughness(self.clearcoat_roughness.max(Self::MIN_ROUGHNESS)),
+            eta: if hit_record.front_face() { self.ior } else { 1.0 / self.ior },
+            weights: weights.map(|weight| weight / total)
+        }
+    }
+
+    /// Returns BSDF multiplied by cosine and mixture density of local directions `wo` and `wi`.
+    fn evaluate_local(&self, lobes: &Lobes, wo: Vec3<f64>, wi: Vec3<f64>) -> (RGB, f64) {
+        let [diffuse_weight, specular_weight, clearcoat_weight, transmission_weight] = lobes.weights;
+        let dielectric = 1.0 - lobes.metallic;
+
+        if wi.z < 0.0 {
+            return self.evaluate_transmission(lobes, wo, wi, transmission_weight);
+        }
+
+        let h = (wo + wi).normalize();
+        let (cos_o, cos_i, cos_d) = (wo.z, wi.z, wo.dot(&h));
+        let mut value = RGB::default();
+        let mut pdf = 0.0;
+
+        // Burley diffuse with grazing retroreflection, and sheen.
+        let schlick_weight = |cosine: f64| (1.0 - cosine).clamp(0.0, 1.0).powi(5);
+        let roughness = lobes.specular.alpha().sqrt();
+        let fd90 = 0.5 + 2.0 * roughness * cos_d * cos_d;
+        let diffuse = (1.0 + (fd90 - 1.0) * schlick_weight(cos_i)) * (1.0 + (fd90 - 1.0) * schlick_weight(cos_o)) * FRAC_1_PI;
+        let sheen_color = RGB(1.0, 1.0, 1.0) * (1.0 - self.sheen_tint) + lobes.base_color * self.sheen_tint;
+        let sheen = sheen_color * (self.sheen * schlick_weight(cos_d));
+        value += (lobes.base_color * diffuse + sheen) * (dielectric * (1.0 - self.transmission) * cos_i);
+        pdf += diffuse_weight * cos_i * FRAC_1_PI;
+
+        let specular_tint = RGB(1.0, 1.0, 1.0) * (0.08 * self.specular);
+        let f0 = specular_tint * dielectric + lobes.base_color * lobes.metallic;
+        let d = lobes.specular.d(h);
+        value += fresnel_schlick(cos_d, f0) * (d * lobes.specular.g2(wo, wi) / (4.0 * cos_o));
+        pdf += specular_weight * lobes.specular.visible_normal_pdf(wo, h) / (4.0 * cos_d);
+
+        if self.clearcoat > 0.0 {
+            let d = lobes.clearcoat.d(h);
+            let fresnel = fresnel_schlick(cos_d, RGB(0.04, 0.04, 0.04));
+            value += fresnel * (0.25 * self.clearcoat * d * lobes.clearcoat.g2(wo, wi) / (4.0 * cos_o));
+            pdf += clearcoat_weight * lobes.clearcoat.visible_normal_pdf(wo, h) / (4.0 * cos_d);
+        }
+
+        (value, pdf)
+    }
+
+    fn evaluate_transmission(&self, lobes: &Lobes, wo: Vec3<f64>, wi: Vec3<f64>, weight: f64) -> (RGB, f64) {
+        if weight <= 0.0 {
+            return (RGB::default(), 0.0);
+        }
+
+        let mut h = (wi * lobes.eta + wo).normalize();
+        if h.z < 0.0 {
+            h = -h;
+        }
+        if wo.dot(&h) <= 0.0 || wi.dot(&h) >= 0.0 {
+            return (RGB::default(), 0.0);
+        }
+
+        let transmittance = 1.0 - fresnel_dielectric(wo.dot(&h), lobes.eta);
+        let jacobian = wi.dot(&h).abs() / (wi.dot(&h) + wo.dot(&h) / lobes.eta).powi(2);
+        let d = lobes.specular.d(h);
+
+        let value = lobes.base_color * ((1.0 - lobes.metallic) * self.transmission * transmittance * d * lobes.specular.g2(wo, wi) * wo.dot(&h) * jacobian / wo.z);
+        (value, weight * lobes.specular.visible_normal_pdf(wo, h) * jacobian)
+    }
+}
+
+impl<'a> Scatter for Principled<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let onb = ONB::new(hit_record.normal());
+        let wo = onb.project(-ray.direction().normalize());
+        if wo.z <= 0.0 {
+            return None;
+        }
+
+        let lobes = self.lobes(hit_record);
+        let [diffuse_weight, specular_weight, clearcoat_weight, _] = lobes.weights;
+
+        let choice = random_in_range(0.0..1.0);
+        let wi = if choice < diffuse_weight {
+            Vec3::random_cosine_direction()
+        } else if choice < diffuse_weight + specular_weight {
+            (-wo).reflect(lobes.specular.sample_visible_normal(wo))
+        } else if choice < diffuse_weight + specular_weight + clearcoat_weight {
+            (-wo).reflect(lobes.clearcoat.sample_visible_normal(wo))
+        } else {
+            let h = lobes.specular.sample_visible_normal(wo);
+            if fresnel_dielectric(wo.dot(&h), lobes.eta) >= 1.0 {
+                return None;
+            }
+            (-wo).refract(h, 1.0 / lobes.eta)
+        };
+
+        let (value, pdf) = self.evaluate_local(&lobes, wo, wi);
+        if pdf <= 0.0 {
+            return None;
+        }
+
+        Some((Ray::new(hit_record.point(), onb.local(wi)), value / pdf))
+    }
+
+    fn emit(&self, _: &Ray, hit_record: &HitRecord) -> RGB {
+        if hit_record.front_face() {
+            self.emission
+        } else {
+            RGB::default()
+        }
+    }
+
+    fn emission(&self) -> RGB {
+        self.emission
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let onb = ONB::new(hit_record.normal());
+        let (wo, wi) = (onb.project(-ray.direction().normalize()), onb.project(direction.normalize()));
+        if wo.z <= 0.0 || wi.z == 0.0 {
+            return Some((RGB::default(), 0.0));
+        }
+
+        Some(self.evaluate_local(&self.lobes(hit_record), wo, wi))
+    }
+}
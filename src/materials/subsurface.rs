@@ -0,0 +1,155 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3, Walk}, random::random_in_range, rgb::RGB, scatter::Scatter, spectrum::{upsample, WAVELENGTH_COUNT}};
+
+use super::microfacet::fresnel_dielectric;
+
+/// Translucent material scattering light below the surface, e.g. wax, soap, marble or skin.\
+/// Light refracted into the object makes a random walk through it: it travels `mean_free_path` on average
+/// between scattering events (per color channel, so red light usually gets further) until it leaves the object.
+/// If mean free path differs between colors, the walk samples distances for one random channel and weights
+/// every channel by its density over average density of all three (spectral MIS), so white material keeps energy.
+/// Shape must be closed, and every scattering event inside takes one bounce of `Renderer` ray depth,
+/// so objects much bigger than the mean free path need higher `ray_depth`.
+/// ```
+/// use rayimg::{materials::Subsurface, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+///
+/// let wax = Subsurface::new(RGB(0.9, 0.8, 0.6), RGB(0.5, 0.3, 0.2)).ior(1.44);
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+/// let mut hit_record = HitRecord::new(10.0, Vec3::new(0.0, 0.0, -10.0));
+/// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, -1.0));
+///
+/// // Light can't pass 10 mean free paths without scattering.
+/// let (scattered, _) = wax.scatter(&ray, &hit_record).unwrap();
+/// assert!(scattered.origin().z > -10.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Subsurface {
+    albedo: RGB,
+    mean_free_path: RGB,
+    ior: f64
+}
+
+impl Subsurface {
+    /// Creates new `Subsurface` material with refraction index 1.4.\
+    /// `albedo` is the color of thick object, converted to albedo of single scattering event inside.
+    pub fn new(albedo: RGB, mean_free_path: RGB) -> Self {
+        Self {
+            albedo,
+            mean_free_path,
+            ior: 1.4
+        }
+    }
+
+    /// Sets refraction index of the surface.
+    pub fn ior(mut self, ior: f64) -> Self {
+        self.ior = ior;
+        self
+    }
+
+    /// Returns true if scattering distance differs between colors, so spectral rendering traces the walk for hero wavelength only.
+    fn is_chromatic(&self) -> bool {
+        self.mean_free_path.0 != self.mean_free_path.1 || self.mean_free_path.1 != self.mean_free_path.2
+    }
+
+    /// Inverts albedo of thick object into albedo of single scattering event using fit by Chiang et al.\
+    /// White stays exactly white, since the fit is off by about 1e-11 there and long walks would lose energy.
+    fn single_scattering_albedo(albedo: f64) -> f64 {
+        if albedo >= 1.0 {
+            return 1.0;
+        }
+
+        let albedo = albedo.max(0.0);
+        (1.0 - (4.09712 + 4.20863 * albedo - (9.59217 + 41.6808 * albedo + 17.7126 * albedo * albedo).sqrt()).powi(2)).clamp(0.0, 1.0)
+    }
+
+    /// Returns albedo of scattering event and extinction coefficient per channel.
+    /// Both are the same for all channels if the ray carries single wavelength.
+    fn coefficients(&self, ray: &Ray) -> (RGB, RGB) {
+        let extinction = |mean_free_path: f64| 1.0 / mean_free_path.max(f64::EPSILON);
+
+        match ray.wavelength() {
+            Some(wavelength) if self.is_chromatic() => {
+                let at_wavelength = |color: RGB| upsample(color, &[wavelength; WAVELENGTH_COUNT])[0];
+                let albedo = Self::single_scattering_albedo(at_wavelength(self.albedo));
+                let extinction = extinction(at_wavelength(self.mean_free_path));
+                (RGB(albedo, albedo, albedo), RGB(extinction, extinction, extinction))
+            },
+            _ => (
+                RGB(
+                    Self::single_scattering_albedo(self.albedo.0),
+                    Self::single_scattering_albedo(self.albedo.1),
+                    Self::single_scattering_albedo(self.albedo.2)
+                ),
+                RGB(extinction(self.mean_free_path.0), extinction(self.mean_free_path.1), extinction(self.mean_free_path.2))
+            )
+        }
+    }
+
+    /// Returns weight of walk per channel: its density for the channel over average density for all channels,
+    /// which is MIS weight of the walk sampled for random channel.
+    fn walk_weight(walk: &Walk, extinction: RGB) -> RGB {
+        let log_density = |extinction: f64| walk.events as f64 * extinction.ln() - extinction * walk.distance;
+        let log_densities = [log_density(extinction.0), log_density(extinction.1), log_density(extinction.2)];
+        let weight = |channel: usize| 3.0 / log_densities.iter().map(|log_density| (log_density - log_densities[channel]).exp()).sum::<f64>();
+        RGB(weight(0), weight(1), weight(2))
+    }
+
+    /// Reflects or refracts ray at the surface according to Fresnel reflectance.
+    /// Ray staying inside the object continues the `walk`.
+    fn cross_boundary(&self, ray: &Ray, hit_record: &HitRecord, walk: Walk) -> Ray {
+        let unit_direction = ray.direction().normalize();
+        let normal = hit_record.normal();
+        let eta = if hit_record.front_face() { self.ior } else { 1.0 / self.ior };
+
+        let direction = if fresnel_dielectric((-unit_direction).dot(&normal), eta) > random_in_range(0.0..1.0) {
+            unit_direction.reflect(normal)
+        } else {
+            unit_direction.refract(normal, 1.0 / eta)
+        };
+
+        let scattered = Ray::new(hit_record.point(), direction);
+        if (direction.dot(&normal) < 0.0) == hit_record.front_face() {
+            scattered.with_walk(walk)
+        } else {
+            scattered
+        }
+    }
+}
+
+impl Scatter for Subsurface {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let new_walk = || Walk { channel: random_in_range(0..3), ..Walk::default() };
+        if hit_record.front_face() {
+            return Some((self.cross_boundary(ray, hit_record, new_walk()), RGB(1.0, 1.0, 1.0)));
+        }
+
+        let (albedo, extinction) = self.coefficients(ray);
+        let walk = ray.walk().unwrap_or_else(new_walk);
+        let distance = hit_record.t() * ray.direction().len();
+        let sampled = -random_in_range(f64::EPSILON..1.0).ln() / [extinction.0, extinction.1, extinction.2][walk.channel];
+
+        let (scattered, next, albedo) = if sampled >= distance {
+            let next = Walk { distance: walk.distance + distance, ..walk };
+            (self.cross_boundary(ray, hit_record, next), next, RGB(1.0, 1.0, 1.0))
+        } else {
+            let next = Walk { events: walk.events + 1, distance: walk.distance + sampled, ..walk };
+            let point = ray.origin() + ray.direction().normalize() * sampled;
+            (Ray::new(point, Vec3::random_unit_vector()).with_walk(next), next, albedo)
+        };
+
+        // Weights of the walk so far telescope into its MIS weight.
+        let (previous, current) = (Self::walk_weight(&walk, extinction), Self::walk_weight(&next, extinction));
+        let ratio = |current: f64, previous: f64| if previous > 0.0 { current / previous } else { 0.0 };
+        let weight = albedo * RGB(ratio(current.0, previous.0), ratio(current.1, previous.1), ratio(current.2, previous.2));
+
+        let scattered = match ray.wavelength() {
+            Some(wavelength) => scattered.with_wavelength(wavelength),
+            None => scattered
+        };
+
+        Some((scattered, weight))
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.is_chromatic()
+    }
+}
@@ -0,0 +1,152 @@
+use crate::{rgb::RGB, spectrum::{upsample, wavelength_weight, WAVELENGTH_COUNT, WAVELENGTH_MAX, WAVELENGTH_MIN}};
+
+use std::f64::consts::PI;
+
+/// Transparent film coating the surface, e.g. soap, oil or oxide layer of anodized metal.\
+/// Light reflected from both sides of the film interferes, so reflectance depends on wavelength and angle
+/// and the surface shows iridescent colors. Coating is applied by `Metal::thin_film` and `Dielectric::thin_film`.
+/// Reflectance of RGB rays is precomputed, rays of single wavelength (spectral rendering or dispersion) use exact value.
+/// ```
+/// use rayimg::{materials::{Dielectric, ThinFilm}, RGB};
+///
+/// let soap_bubble = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.0).thin_film(ThinFilm::new(400.0, 1.33));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ThinFilm {
+    thickness: f64,
+    ior: f64,
+    table: Vec<[RGB; 2]>
+}
+
+/// Material below the film.
+pub(crate) enum Substrate {
+    /// Dielectric with refraction index.
+    Dielectric(f64),
+    /// Mirror with reflectance of every channel independent of angle, like `Metal`.
+    Mirror(RGB)
+}
+
+impl ThinFilm {
+    const COSINE_STEPS: usize = 32;
+    const AMPLITUDE_STEPS: usize = 32;
+    const WAVELENGTH_STEPS: usize = 40;
+
+    /// Creates new `ThinFilm` with `thickness` in nanometers and refraction index `ior`.
+    pub fn new(thickness: f64, ior: f64) -> Self {
+        let mut film = Self {
+            thickness,
+            ior,
+            table: Vec::new()
+        };
+
+        film.table = film.precompute();
+        film
+    }
+
+    /// Returns reflectance of the coated surface for light coming from outside with cosine `cos_i` to the normal.
+    pub(crate) fn reflectance(&self, cos_i: f64, substrate: &Substrate, wavelength: Option<f64>) -> RGB {
+        let cos_i = cos_i.clamp(0.0, 1.0);
+        // Mirror shifts phase by half wave, and p amplitudes have opposite sign by convention of Fresnel equations.
+        let amplitudes = |reflectance: f64| {
+            let amplitude = reflectance.clamp(0.0, 1.0).sqrt();
+            (-amplitude, amplitude)
+        };
+
+        match (wavelength, substrate) {
+            (Some(wavelength), Substrate::Dielectric(ior)) => {
+                let reflectance = self.exact(cos_i, self.dielectric_amplitudes(cos_i, *ior), wavelength);
+                RGB(reflectance, reflectance, reflectance)
+            },
+            (Some(wavelength), Substrate::Mirror(color)) => {
+                let reflectance = self.exact(cos_i, amplitudes(upsample(*color, &[wavelength; WAVELENGTH_COUNT])[0]), wavelength);
+                RGB(reflectance, reflectance, reflectance)
+            },
+            (None, Substrate::Dielectric(ior)) => self.lookup(cos_i, self.dielectric_amplitudes(cos_i, *ior)),
+            (None, Substrate::Mirror(color)) => RGB(
+                self.lookup(cos_i, amplitudes(color.0)).0,
+                self.lookup(cos_i, amplitudes(color.1)).1,
+                self.lookup(cos_i, amplitudes(color.2)).2
+            )
+        }
+    }
+
+    /// Returns cosine of refraction angle inside the film.
+    fn film_cosine(&self, cos_i: f64) -> f64 {
+        (1.0 - (1.0 - cos_i * cos_i) / (self.ior * self.ior)).max(0.0).sqrt()
+    }
+
+    /// Returns amplitudes of s and p polarized light reflected from the boundary between film and dielectric.
+    fn dielectric_amplitudes(&self, cos_i: f64, ior: f64) -> (f64, f64) {
+        let sin2 = (1.0 - cos_i * cos_i) / (ior * ior);
+        if sin2 >= 1.0 {
+            return (1.0, 1.0);
+        }
+
+        let (cos_t, cos_s) = (self.film_cosine(cos_i), (1.0 - sin2).sqrt());
+        (
+            (self.ior * cos_t - ior * cos_s) / (self.ior * cos_t + ior * cos_s),
+            (ior * cos_t - self.ior * cos_s) / (ior * cos_t + self.ior * cos_s)
+        )
+    }
+
+    /// Airy reflectance of the film for both polarizations, given amplitudes reflected by the substrate.
+    fn polarized(&self, cos_i: f64, amplitudes: (f64, f64), wavelength: f64) -> [f64; 2] {
+        let cos_t = self.film_cosine(cos_i);
+        let outer = [
+            (cos_i - self.ior * cos_t) / (cos_i + self.ior * cos_t),
+            (self.ior * cos_i - cos_t) / (self.ior * cos_i + cos_t)
+        ];
+        let phase = (4.0 * PI * self.ior * self.thickness * cos_t / wavelength).cos();
+
+        [(outer[0], amplitudes.0), (outer[1], amplitudes.1)].map(|(r12, r23)| {
+            let interference = 2.0 * r12 * r23 * phase;
+            ((r12 * r12 + r23 * r23 + interference) / (1.0 + r12 * r12 * r23 * r23 + interference)).clamp(0.0, 1.0)
+        })
+    }
+
+    fn exact(&self, cos_i: f64, amplitudes: (f64, f64), wavelength: f64) -> f64 {
+        let [s, p] = self.polarized(cos_i, amplitudes, wavelength);
+        0.5 * (s + p)
+    }
+
+    /// Tabulates RGB reflectance of both polarizations over cosines and substrate amplitudes.
+    fn precompute(&self) -> Vec<[RGB; 2]> {
+        let mut table = Vec::with_capacity((Self::COSINE_STEPS + 1) * (Self::AMPLITUDE_STEPS + 1));
+        for cosine in 0..=Self::COSINE_STEPS {
+            let cos_i = cosine as f64 / Self::COSINE_STEPS as f64;
+            for amplitude in 0..=Self::AMPLITUDE_STEPS {
+                let amplitude = 2.0 * amplitude as f64 / Self::AMPLITUDE_STEPS as f64 - 1.0;
+
+                let mut sum = [RGB::default(); 2];
+                for step in 0..Self::WAVELENGTH_STEPS {
+                    let wavelength = WAVELENGTH_MIN + (step as f64 + 0.5) / Self::WAVELENGTH_STEPS as f64 * (WAVELENGTH_MAX - WAVELENGTH_MIN);
+                    let weight = wavelength_weight(wavelength) / Self::WAVELENGTH_STEPS as f64;
+                    let [s, p] = self.polarized(cos_i, (amplitude, amplitude), wavelength);
+                    sum[0] += weight * s;
+                    sum[1] += weight * p;
+                }
+                table.push(sum);
+            }
+        }
+
+        table
+    }
+
+    /// Interpolates precomputed reflectance bilinearly.
+    fn lookup(&self, cos_i: f64, amplitudes: (f64, f64)) -> RGB {
+        let x = cos_i * Self::COSINE_STEPS as f64;
+        let x0 = (x as usize).min(Self::COSINE_STEPS - 1);
+        let fx = x - x0 as f64;
+
+        let [s, p] = [amplitudes.0, amplitudes.1].map(|amplitude| (amplitude.clamp(-1.0, 1.0) + 1.0) * 0.5 * Self::AMPLITUDE_STEPS as f64);
+        let interpolate = |y: f64, polarization: usize| {
+            let y0 = (y as usize).min(Self::AMPLITUDE_STEPS - 1);
+            let fy = y - y0 as f64;
+            let at = |x: usize, y: usize| self.table[x * (Self::AMPLITUDE_STEPS + 1) + y][polarization];
+
+            (at(x0, y0) * (1.0 - fy) + at(x0, y0 + 1) * fy) * (1.0 - fx) + (at(x0 + 1, y0) * (1.0 - fy) + at(x0 + 1, y0 + 1) * fy) * fx
+        };
+
+        (interpolate(s, 0) + interpolate(p, 1)) * 0.5
+    }
+}
@@ -0,0 +1,66 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, rgb::RGB, scatter::Scatter};
+
+use std::sync::Arc;
+
+/// Uses different materials for front and back faces of thin surfaces, e.g. printed paper.\
+/// Back faces are presented to the `back` material as front faces, so one-sided materials like `DiffuseLight` work on both sides.
+/// ```
+/// use rayimg::{materials::{DiffuseLight, Lambertian, TwoSided}, math::{Ray, Vec3}, HitRecord, Scatter, RGB};
+///
+/// let lamp_shade = TwoSided::new(Lambertian::new(RGB(0.9, 0.9, 0.8)), DiffuseLight::new(RGB(4.0, 4.0, 3.0)));
+/// let ray = Ray::new(Vec3::new(0.0, 0.0, -1.0), Vec3::new(0.0, 0.0, 1.0));
+/// let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+/// hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+/// assert_eq!(lamp_shade.emit(&ray, &hit_record), RGB(4.0, 4.0, 3.0));
+/// ```
+pub struct TwoSided<'a> {
+    front: Arc<dyn Scatter + 'a + Send + Sync>,
+    back: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> TwoSided<'a> {
+    /// Creates new `TwoSided` material.
+    pub fn new(front: impl Scatter + 'a + Send + Sync, back: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            front: Arc::new(front),
+            back: Arc::new(back)
+        }
+    }
+
+    /// Returns material of the face that was hit and hit record as seen from its front.
+    fn side<'b>(&self, ray: &Ray, hit_record: &HitRecord<'b>) -> (&dyn Scatter, HitRecord<'b>) {
+        if hit_record.front_face() {
+            return (&*self.front, *hit_record);
+        }
+
+        let mut flipped = *hit_record;
+        flipped.set_face_normal(ray, hit_record.geometric_normal());
+        flipped.set_shading_normal(hit_record.normal());
+        (&*self.back, flipped)
+    }
+}
+
+impl<'a> Scatter for TwoSided<'a> {
+    fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)> {
+        let (material, hit_record) = self.side(ray, hit_record);
+        material.scatter(ray, &hit_record)
+    }
+
+    fn emit(&self, ray: &Ray, hit_record: &HitRecord) -> RGB {
+        let (material, hit_record) = self.side(ray, hit_record);
+        material.emit(ray, &hit_record)
+    }
+
+    fn emission(&self) -> RGB {
+        self.front.emission() + self.back.emission()
+    }
+
+    fn is_dispersive(&self) -> bool {
+        self.front.is_dispersive() || self.back.is_dispersive()
+    }
+
+    fn evaluate(&self, ray: &Ray, hit_record: &HitRecord, direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        let (material, hit_record) = self.side(ray, hit_record);
+        material.evaluate(ray, &hit_record, direction)
+    }
+}
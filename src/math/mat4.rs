@@ -0,0 +1,173 @@
+use super::vec3::Vec3;
+
+use std::ops::Mul;
+
+/// 4x4 matrix transforming column vectors in homogeneous coordinates.
+/// ```
+/// # use rayimg::math::{Mat4, Vec3};
+/// let matrix = Mat4::translation(Vec3::new(1.0, 2.0, 3.0)) * Mat4::scaling(Vec3::new(2.0, 2.0, 2.0));
+/// assert_eq!(matrix.transform_point(Vec3::new(1.0, 1.0, 1.0)), Vec3::new(3.0, 4.0, 5.0));
+/// assert_eq!(matrix.transform_vector(Vec3::new(1.0, 1.0, 1.0)), Vec3::new(2.0, 2.0, 2.0));
+/// assert_eq!(matrix * matrix.inverse().unwrap(), Mat4::identity());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Mat4 {
+    pub rows: [[f64; 4]; 4]
+}
+
+impl Mat4 {
+    /// Creates new `Mat4` from rows.
+    pub fn new(rows: [[f64; 4]; 4]) -> Self {
+        Self {
+            rows
+        }
+    }
+
+    /// Returns identity matrix.
+    pub fn identity() -> Self {
+        Self::scaling(Vec3::new(1.0, 1.0, 1.0))
+    }
+
+    /// Returns matrix moving points by `offset`.
+    pub fn translation(offset: Vec3<f64>) -> Self {
+        Self::new([
+            [1.0, 0.0, 0.0, offset.x],
+            [0.0, 1.0, 0.0, offset.y],
+            [0.0, 0.0, 1.0, offset.z],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    /// Returns matrix scaling along axes by `factors`.
+    pub fn scaling(factors: Vec3<f64>) -> Self {
+        Self::new([
+            [factors.x, 0.0, 0.0, 0.0],
+            [0.0, factors.y, 0.0, 0.0],
+            [0.0, 0.0, factors.z, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    /// Returns matrix rotating counterclockwise around `axis` by `angle` in **degrees**.
+    /// ```
+    /// # use rayimg::math::{Mat4, Vec3};
+    /// let rotated = Mat4::rotation(Vec3::new(0.0, 0.0, 1.0), 90.0).transform_vector(Vec3::new(1.0, 0.0, 0.0));
+    /// assert!((rotated - Vec3::new(0.0, 1.0, 0.0)).len() < 1e-12);
+    /// ```
+    pub fn rotation(axis: Vec3<f64>, angle: f64) -> Self {
+        let Vec3 { x, y, z } = axis.normalize();
+        let (sin, cos) = angle.to_radians().sin_cos();
+        let k = 1.0 - cos;
+
+        Self::new([
+            [cos + x * x * k, x * y * k - z * sin, x * z * k + y * sin, 0.0],
+            [y * x * k + z * sin, cos + y * y * k, y * z * k - x * sin, 0.0],
+            [z * x * k - y * sin, z * y * k + x * sin, cos + z * z * k, 0.0],
+            [0.0, 0.0, 0.0, 1.0]
+        ])
+    }
+
+    /// Returns transposed matrix.
+    pub fn transpose(&self) -> Self {
+        let mut rows = [[0.0; 4]; 4];
+        for (row, values) in rows.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = self.rows[column][row];
+            }
+        }
+
+        Self::new(rows)
+    }
+
+    /// Returns determinant of the matrix.
+    pub fn determinant(&self) -> f64 {
+        self.eliminate().1
+    }
+
+    /// Returns inverse matrix, or None if the matrix is singular.
+    pub fn inverse(&self) -> Option<Self> {
+        match self.eliminate() {
+            (Some(inverse), _) => Some(inverse),
+            _ => None
+        }
+    }
+
+    /// Gauss-Jordan elimination with partial pivoting, returning inverse and determinant.
+    fn eliminate(&self) -> (Option<Self>, f64) {
+        let (mut left, mut right) = (self.rows, Self::identity().rows);
+        let mut determinant = 1.0;
+
+        for column in 0..4 {
+            let pivot = (column..4).max_by(|&a, &b| left[a][column].abs().total_cmp(&left[b][column].abs())).unwrap();
+            if left[pivot][column].abs() < 1e-12 {
+                return (None, 0.0);
+            }
+
+            if pivot != column {
+                left.swap(pivot, column);
+                right.swap(pivot, column);
+                determinant = -determinant;
+            }
+
+            let scale = left[column][column];
+            determinant *= scale;
+            for index in 0..4 {
+                left[column][index] /= scale;
+                right[column][index] /= scale;
+            }
+
+            for row in (0..4).filter(|&row| row != column) {
+                let factor = left[row][column];
+                for index in 0..4 {
+                    left[row][index] -= factor * left[column][index];
+                    right[row][index] -= factor * right[column][index];
+                }
+            }
+        }
+
+        (Some(Self::new(right)), determinant)
+    }
+
+    /// Transforms point, applying translation.
+    pub fn transform_point(&self, point: Vec3<f64>) -> Vec3<f64> {
+        let row = |index: usize| {
+            let row = self.rows[index];
+            row[0] * point.x + row[1] * point.y + row[2] * point.z + row[3]
+        };
+
+        let w = row(3);
+        let point = Vec3::new(row(0), row(1), row(2));
+        if w == 1.0 { point } else { point / w }
+    }
+
+    /// Transforms direction, ignoring translation.
+    pub fn transform_vector(&self, vector: Vec3<f64>) -> Vec3<f64> {
+        let row = |index: usize| {
+            let row = self.rows[index];
+            row[0] * vector.x + row[1] * vector.y + row[2] * vector.z
+        };
+
+        Vec3::new(row(0), row(1), row(2))
+    }
+}
+
+impl Default for Mat4 {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Mul<Mat4> for Mat4 {
+    type Output = Mat4;
+
+    fn mul(self, other: Mat4) -> Mat4 {
+        let mut rows = [[0.0; 4]; 4];
+        for (row, values) in rows.iter_mut().enumerate() {
+            for (column, value) in values.iter_mut().enumerate() {
+                *value = (0..4).map(|index| self.rows[row][index] * other.rows[index][column]).sum();
+            }
+        }
+
+        Mat4::new(rows)
+    }
+}
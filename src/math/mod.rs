@@ -1,5 +1,14 @@
 mod vec3;
 mod ray;
+mod onb;
+mod perlin;
+mod mat4;
+mod transform;
 
 pub use self::vec3::Vec3;
 pub use self::ray::Ray;
+pub(crate) use self::ray::Walk;
+pub use self::onb::ONB;
+pub use self::perlin::Perlin;
+pub use self::mat4::Mat4;
+pub use self::transform::Transform;
@@ -0,0 +1,62 @@
+use super::vec3::Vec3;
+
+/// Orthonormal basis built around a single direction `w`.
+/// ```
+/// # use rayimg::math::{Vec3, ONB};
+/// let onb = ONB::new(Vec3::new(0.0, 0.0, 2.0));
+/// assert_eq!(onb.w(), Vec3::new(0.0, 0.0, 1.0));
+/// assert!(onb.local(Vec3::new(0.0, 0.0, 1.0)).z > 0.999);
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct ONB {
+    u: Vec3<f64>,
+    v: Vec3<f64>,
+    w: Vec3<f64>
+}
+
+impl ONB {
+    /// Creates new `ONB` with `w` axis pointing along given direction.
+    pub fn new(direction: Vec3<f64>) -> Self {
+        let w = direction.normalize();
+        let a = if w.x.abs() > 0.9 { Vec3::new(0.0, 1.0, 0.0) } else { Vec3::new(1.0, 0.0, 0.0) };
+        let v = w.cross(&a).normalize();
+        let u = w.cross(&v);
+
+        Self {
+            u,
+            v,
+            w
+        }
+    }
+
+    /// Returns first tangent axis.
+    pub fn u(&self) -> Vec3<f64> {
+        self.u
+    }
+
+    /// Returns second tangent axis.
+    pub fn v(&self) -> Vec3<f64> {
+        self.v
+    }
+
+    /// Returns main axis.
+    pub fn w(&self) -> Vec3<f64> {
+        self.w
+    }
+
+    /// Transforms vector from basis coordinates to world coordinates.
+    pub fn local(&self, a: Vec3<f64>) -> Vec3<f64> {
+        self.u * a.x + self.v * a.y + self.w * a.z
+    }
+
+    /// Transforms vector from world coordinates to basis coordinates.
+    /// ```
+    /// # use rayimg::math::{Vec3, ONB};
+    /// let onb = ONB::new(Vec3::new(1.0, 2.0, 3.0));
+    /// let a = Vec3::new(0.3, -0.2, 0.5);
+    /// assert!((onb.local(onb.project(a)) - a).near_epsilon(1e-12));
+    /// ```
+    pub fn project(&self, a: Vec3<f64>) -> Vec3<f64> {
+        Vec3::new(a.dot(&self.u), a.dot(&self.v), a.dot(&self.w))
+    }
+}
@@ -0,0 +1,96 @@
+use super::vec3::Vec3;
+use crate::random::random_in_range;
+
+/// Perlin gradient noise.
+/// ```
+/// # use rayimg::math::{Perlin, Vec3};
+/// let perlin = Perlin::new();
+/// let value = perlin.noise(Vec3::new(0.3, 1.7, -2.2));
+/// assert!(-1.0 <= value && value <= 1.0);
+/// assert_eq!(perlin.noise(Vec3::new(1.0, 2.0, 3.0)), 0.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Perlin {
+    gradients: Vec<Vec3<f64>>,
+    permutations: [Vec<usize>; 3]
+}
+
+impl Perlin {
+    const POINT_COUNT: usize = 256;
+
+    /// Creates new `Perlin` noise with random gradients.
+    pub fn new() -> Self {
+        Self {
+            gradients: (0..Self::POINT_COUNT).map(|_| Vec3::random_unit_vector()).collect(),
+            permutations: [Self::permutation(), Self::permutation(), Self::permutation()]
+        }
+    }
+
+    /// Returns smooth noise value in interval `-1.0..=1.0` which is zero at integer points.
+    pub fn noise(&self, point: Vec3<f64>) -> f64 {
+        let (u, v, w) = (point.x - point.x.floor(), point.y - point.y.floor(), point.z - point.z.floor());
+        let (i, j, k) = (point.x.floor() as i64, point.y.floor() as i64, point.z.floor() as i64);
+
+        let mut corners = [[[Vec3::default(); 2]; 2]; 2];
+        for (di, plane) in corners.iter_mut().enumerate() {
+            for (dj, row) in plane.iter_mut().enumerate() {
+                for (dk, corner) in row.iter_mut().enumerate() {
+                    let index = self.permutations[0][Self::wrap(i + di as i64)]
+                        ^ self.permutations[1][Self::wrap(j + dj as i64)]
+                        ^ self.permutations[2][Self::wrap(k + dk as i64)];
+                    *corner = self.gradients[index];
+                }
+            }
+        }
+
+        Self::interpolate(&corners, u, v, w)
+    }
+
+    /// Returns absolute value of noise summed over `depth` octaves with doubling frequency and halving amplitude.
+    pub fn turbulence(&self, point: Vec3<f64>, depth: usize) -> f64 {
+        let (mut accumulator, mut point, mut weight) = (0.0, point, 1.0);
+        for _ in 0..depth {
+            accumulator += weight * self.noise(point);
+            weight *= 0.5;
+            point *= 2.0;
+        }
+
+        accumulator.abs()
+    }
+
+    fn wrap(index: i64) -> usize {
+        index.rem_euclid(Self::POINT_COUNT as i64) as usize
+    }
+
+    fn permutation() -> Vec<usize> {
+        let mut permutation = (0..Self::POINT_COUNT).collect::<Vec<usize>>();
+        for i in (1..Self::POINT_COUNT).rev() {
+            permutation.swap(i, random_in_range(0..=i));
+        }
+
+        permutation
+    }
+
+    fn interpolate(corners: &[[[Vec3<f64>; 2]; 2]; 2], u: f64, v: f64, w: f64) -> f64 {
+        let (uu, vv, ww) = (u * u * (3.0 - 2.0 * u), v * v * (3.0 - 2.0 * v), w * w * (3.0 - 2.0 * w));
+
+        let mut accumulator = 0.0;
+        for (i, plane) in corners.iter().enumerate() {
+            for (j, row) in plane.iter().enumerate() {
+                for (k, gradient) in row.iter().enumerate() {
+                    let (i, j, k) = (i as f64, j as f64, k as f64);
+                    let weight = Vec3::new(u - i, v - j, w - k);
+                    accumulator += (i * uu + (1.0 - i) * (1.0 - uu)) * (j * vv + (1.0 - j) * (1.0 - vv)) * (k * ww + (1.0 - k) * (1.0 - ww)) * gradient.dot(&weight);
+                }
+            }
+        }
+
+        accumulator
+    }
+}
+
+impl Default for Perlin {
+    fn default() -> Self {
+        Self::new()
+    }
+}
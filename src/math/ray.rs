@@ -1,10 +1,24 @@
 use super::vec3::Vec3;
 
-/// Ray is an object which consists of `origin` and `direction`.
+/// Ray is an object which consists of `origin` and `direction`.\
+/// Rays split by dispersion also carry single `wavelength` of light.
 #[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
 pub struct Ray {
     origin: Vec3<f64>,
-    direction: Vec3<f64>
+    direction: Vec3<f64>,
+    wavelength: Option<f64>,
+    walk: Option<Walk>
+}
+
+/// State of random walk inside scattering medium, carried by rays between its scattering events.
+#[derive(Debug, Default, Clone, Copy, PartialEq, PartialOrd)]
+pub(crate) struct Walk {
+    /// Color channel whose extinction samples distances of the walk.
+    pub channel: usize,
+    /// Number of scattering events so far.
+    pub events: u32,
+    /// Distance travelled inside the medium so far.
+    pub distance: f64
 }
 
 impl Ray {
@@ -17,10 +31,40 @@ impl Ray {
     pub fn new(origin: Vec3<f64>, direction: Vec3<f64>) -> Self {
         Self {
             origin,
-            direction
+            direction,
+            wavelength: None,
+            walk: None
         }
     }
 
+    /// Sets wavelength of light carried by the ray in nanometers.
+    /// ```
+    /// # use rayimg::math::{Vec3, Ray};
+    /// let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -1.0));
+    /// assert_eq!(ray.wavelength(), None);
+    /// assert_eq!(ray.with_wavelength(550.0).wavelength(), Some(550.0));
+    /// ```
+    pub fn with_wavelength(mut self, wavelength: f64) -> Self {
+        self.wavelength = Some(wavelength);
+        self
+    }
+
+    /// Returns wavelength of light carried by the ray in nanometers, or None if ray carries all visible wavelengths.
+    pub fn wavelength(&self) -> Option<f64> {
+        self.wavelength
+    }
+
+    /// Sets state of random walk inside scattering medium.
+    pub(crate) fn with_walk(mut self, walk: Walk) -> Self {
+        self.walk = Some(walk);
+        self
+    }
+
+    /// Returns state of random walk, or None if ray is not inside scattering medium.
+    pub(crate) fn walk(&self) -> Option<Walk> {
+        self.walk
+    }
+
     /// Returns origin of ray.
     /// ```
     /// # use rayimg::math::{Vec3, Ray};
@@ -0,0 +1,127 @@
+use super::{mat4::Mat4, ray::Ray, vec3::Vec3};
+use crate::AABB;
+
+/// Affine transformation together with its inverse, e.g. placement of an object in the scene.\
+/// Transformations are applied in order of calls, so the object below is scaled first and moved last.
+/// ```
+/// # use rayimg::math::{Transform, Vec3};
+/// let transform = Transform::new().scale(Vec3::new(2.0, 2.0, 2.0)).rotate(Vec3::new(0.0, 1.0, 0.0), 90.0).translate(Vec3::new(0.0, 0.0, -5.0));
+/// assert!((transform.point(Vec3::new(1.0, 0.0, 0.0)) - Vec3::new(0.0, 0.0, -7.0)).len() < 1e-12);
+/// assert!((transform.inverse().point(Vec3::new(0.0, 0.0, -7.0)) - Vec3::new(1.0, 0.0, 0.0)).len() < 1e-12);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Transform {
+    matrix: Mat4,
+    inverse: Mat4
+}
+
+impl Transform {
+    /// Creates identity `Transform`.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates `Transform` from matrix, or returns None if it is not invertible.
+    pub fn from_matrix(matrix: Mat4) -> Option<Self> {
+        Some(Self {
+            matrix,
+            inverse: matrix.inverse()?
+        })
+    }
+
+    /// Applies `other` transformation after this one.
+    pub fn then(self, other: Transform) -> Self {
+        Self {
+            matrix: other.matrix * self.matrix,
+            inverse: self.inverse * other.inverse
+        }
+    }
+
+    /// Moves by `offset`.
+    pub fn translate(self, offset: Vec3<f64>) -> Self {
+        self.then(Self {
+            matrix: Mat4::translation(offset),
+            inverse: Mat4::translation(-offset)
+        })
+    }
+
+    /// Scales along axes by non-zero `factors`.
+    pub fn scale(self, factors: Vec3<f64>) -> Self {
+        self.then(Self {
+            matrix: Mat4::scaling(factors),
+            inverse: Mat4::scaling(Vec3::new(1.0 / factors.x, 1.0 / factors.y, 1.0 / factors.z))
+        })
+    }
+
+    /// Rotates counterclockwise around `axis` by `angle` in **degrees**.
+    pub fn rotate(self, axis: Vec3<f64>, angle: f64) -> Self {
+        let rotation = Mat4::rotation(axis, angle);
+        self.then(Self {
+            matrix: rotation,
+            inverse: rotation.transpose()
+        })
+    }
+
+    /// Returns transformation matrix.
+    pub fn matrix(&self) -> Mat4 {
+        self.matrix
+    }
+
+    /// Returns inverse `Transform`.
+    pub fn inverse(&self) -> Self {
+        Self {
+            matrix: self.inverse,
+            inverse: self.matrix
+        }
+    }
+
+    /// Transforms point.
+    pub fn point(&self, point: Vec3<f64>) -> Vec3<f64> {
+        self.matrix.transform_point(point)
+    }
+
+    /// Transforms direction, ignoring translation.
+    pub fn vector(&self, vector: Vec3<f64>) -> Vec3<f64> {
+        self.matrix.transform_vector(vector)
+    }
+
+    /// Transforms surface normal by inverse transposed matrix, so it stays perpendicular to transformed surface.
+    /// Result is not normalized.
+    /// ```
+    /// # use rayimg::math::{Transform, Vec3};
+    /// let squash = Transform::new().scale(Vec3::new(1.0, 0.5, 1.0));
+    /// let (tangent, normal) = (Vec3::new(1.0, -1.0, 0.0), Vec3::new(1.0, 1.0, 0.0));
+    /// assert_eq!(squash.vector(tangent).dot(&squash.normal(normal)), 0.0);
+    /// ```
+    pub fn normal(&self, normal: Vec3<f64>) -> Vec3<f64> {
+        self.inverse.transpose().transform_vector(normal)
+    }
+
+    /// Transforms ray, keeping its parameter `t` of every point.
+    pub fn ray(&self, ray: &Ray) -> Ray {
+        let transformed = Ray::new(self.point(ray.origin()), self.vector(ray.direction()));
+        match ray.wavelength() {
+            Some(wavelength) => transformed.with_wavelength(wavelength),
+            None => transformed
+        }
+    }
+
+    /// Returns bounding box of transformed box.
+    pub fn aabb(&self, aabb: &AABB) -> AABB {
+        if !aabb.is_bounded() {
+            return AABB::universe();
+        }
+
+        let [x, y, z] = aabb.axes;
+        let corners = (0..8).map(|corner| Vec3::new(
+            if corner & 1 == 0 { x.min } else { x.max },
+            if corner & 2 == 0 { y.min } else { y.max },
+            if corner & 4 == 0 { z.min } else { z.max }
+        ));
+
+        corners.map(|corner| {
+            let point = self.point(corner);
+            AABB::from_two_points(point, point)
+        }).reduce(AABB::unite).unwrap()
+    }
+}
@@ -144,6 +144,58 @@ impl<T> Vec3<T> where T: Copy + Add<Output = T> + Sub<Output = T> + Mul<Output =
         }
     }
 
+    /// Returns random `Vec3<T>` of unit length uniformly distributed over the sphere.
+    /// ```
+    /// # use rayimg::math::Vec3;
+    /// let vector = Vec3::<f64>::random_unit_vector();
+    /// assert!((vector.len() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn random_unit_vector() -> Self {
+        loop {
+            let v = Self::random_in_unit_sphere();
+            if v.squared_magnitude() > 1e-16.into() {
+                return v.normalize();
+            }
+        }
+    }
+
+    /// Returns random `Vec3<T>` of unit length with `z >= 0.0` distributed proportionally to cosine of angle with `z` axis.
+    /// ```
+    /// # use rayimg::math::Vec3;
+    /// let vector = Vec3::<f64>::random_cosine_direction();
+    /// assert!(vector.z >= 0.0 && (vector.len() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn random_cosine_direction() -> Self {
+        let (r1, r2): (f64, f64) = (random_in_range(0.0..1.0), random_in_range(0.0..1.0));
+        let phi = 2.0 * std::f64::consts::PI * r1;
+        let r2_sqrt = r2.sqrt();
+
+        Self {
+            x: (phi.cos() * r2_sqrt).into(),
+            y: (phi.sin() * r2_sqrt).into(),
+            z: (1.0 - r2).sqrt().into()
+        }
+    }
+
+    /// Returns random `Vec3<T>` of unit length uniformly distributed inside cone around `z` axis with cosine of half-angle `cos_theta_max`.
+    /// ```
+    /// # use rayimg::math::Vec3;
+    /// let vector = Vec3::<f64>::random_in_cone(0.5);
+    /// assert!(vector.z >= 0.5 && (vector.len() - 1.0).abs() < 1e-9);
+    /// ```
+    pub fn random_in_cone(cos_theta_max: f64) -> Self {
+        let (r1, r2): (f64, f64) = (random_in_range(0.0..1.0), random_in_range(0.0..1.0));
+        let z = 1.0 + r2 * (cos_theta_max - 1.0);
+        let phi = 2.0 * std::f64::consts::PI * r1;
+        let sin_theta = (1.0 - z * z).sqrt();
+
+        Self {
+            x: (phi.cos() * sin_theta).into(),
+            y: (phi.sin() * sin_theta).into(),
+            z: z.into()
+        }
+    }
+
     /// Returns random `Vec3<T>` with `x` and `y` in range `-1.0..1.0` (z = 0.0) and length < 1.0.
     /// ```
     /// # use rayimg::math::Vec3;
@@ -0,0 +1,129 @@
+use crate::{math::{Ray, Vec3}, random::random_in_range, Hit, Scene, AABB};
+
+use std::sync::Arc;
+
+/// Chooses lights proportionally to their power using alias table (Vose's method).
+/// Lights are also kept in bounding volume hierarchy, so evaluating density of some direction
+/// only visits lights whose bounding boxes are crossed by it.
+pub struct LightSampler<'a> {
+    lights: Vec<Arc<dyn Hit + 'a + Send + Sync>>,
+    probabilities: Vec<f64>,
+    aliases: Vec<(f64, usize)>,
+    nodes: Vec<LightNode>
+}
+
+enum LightNode {
+    Leaf(AABB, usize),
+    Branch(AABB, usize, usize)
+}
+
+impl<'a> LightSampler<'a> {
+    pub fn new(lights: Scene<'a>) -> Self {
+        let lights = lights.objects();
+
+        let powers = lights.iter().map(|light| light.power().max(0.0)).collect::<Vec<f64>>();
+        let total_power = powers.iter().sum::<f64>();
+        let probabilities = if total_power > 0.0 {
+            powers.iter().map(|power| power / total_power).collect()
+        } else {
+            vec![1.0 / lights.len() as f64; lights.len()]
+        };
+
+        let mut sampler = Self {
+            aliases: Self::build_aliases(&probabilities),
+            probabilities,
+            lights,
+            nodes: Vec::new()
+        };
+
+        if !sampler.lights.is_empty() {
+            let mut indices = (0..sampler.lights.len()).collect::<Vec<usize>>();
+            sampler.build_node(&mut indices);
+        }
+
+        sampler
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.lights.is_empty()
+    }
+
+    pub fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        let index = random_in_range(0..self.aliases.len());
+        let (threshold, alias) = self.aliases[index];
+        let light = if random_in_range(0.0..1.0) < threshold { index } else { alias };
+
+        self.lights[light].random_direction(origin)
+    }
+
+    pub fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if self.nodes.is_empty() {
+            return 0.0;
+        }
+
+        self.node_pdf_value(self.nodes.len() - 1, &Ray::new(origin, direction))
+    }
+
+    fn node_pdf_value(&self, node: usize, ray: &Ray) -> f64 {
+        match self.nodes[node] {
+            LightNode::Leaf(aabb, light) => {
+                if aabb.hit(ray, 0.001, f64::MAX).is_none() || self.probabilities[light] <= 0.0 {
+                    return 0.0;
+                }
+
+                self.probabilities[light] * self.lights[light].pdf_value(ray.origin(), ray.direction())
+            },
+            LightNode::Branch(aabb, left, right) => {
+                if aabb.hit(ray, 0.001, f64::MAX).is_none() {
+                    return 0.0;
+                }
+
+                self.node_pdf_value(left, ray) + self.node_pdf_value(right, ray)
+            }
+        }
+    }
+
+    /// Builds hierarchy bottom-up, so the root is the last node.
+    fn build_node(&mut self, indices: &mut [usize]) -> usize {
+        if indices.len() == 1 {
+            self.nodes.push(LightNode::Leaf(self.lights[indices[0]].bounding(), indices[0]));
+            return self.nodes.len() - 1;
+        }
+
+        let aabb = indices.iter().map(|&index| self.lights[index].bounding()).reduce(AABB::unite).unwrap();
+        let axis = (0..3).max_by(|&a, &b| aabb.axes[a].len().total_cmp(&aabb.axes[b].len())).unwrap();
+        indices.sort_by(|&a, &b| self.lights[a].bounding().center()[axis].total_cmp(&self.lights[b].bounding().center()[axis]));
+
+        let mid = indices.len() / 2;
+        let left = self.build_node(&mut indices[..mid]);
+        let right = self.build_node(&mut indices[mid..]);
+
+        self.nodes.push(LightNode::Branch(aabb, left, right));
+        self.nodes.len() - 1
+    }
+
+    fn build_aliases(probabilities: &[f64]) -> Vec<(f64, usize)> {
+        let count = probabilities.len();
+        let mut aliases = vec![(1.0, 0); count];
+        let mut scaled = probabilities.iter().map(|probability| probability * count as f64).collect::<Vec<f64>>();
+
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) = (0..count).partition(|&index| scaled[index] < 1.0);
+        while let (Some(&less), Some(&more)) = (small.last(), large.last()) {
+            small.pop();
+            aliases[less] = (scaled[less], more);
+
+            scaled[more] -= 1.0 - scaled[less];
+            if scaled[more] < 1.0 {
+                large.pop();
+                small.push(more);
+            }
+        }
+
+        // Leftovers are only caused by rounding errors and must be chosen with certainty.
+        for index in small.into_iter().chain(large) {
+            aliases[index] = (1.0, index);
+        }
+
+        aliases
+    }
+}
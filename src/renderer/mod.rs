@@ -1,7 +1,9 @@
 mod renderer_builder;
+mod light_sampler;
 
-use crate::{image_write::ImageWrite, rgb::RGB, camera::Camera, math::Ray, hit::Hit, random::random_in_range};
+use crate::{image_write::ImageWrite, rgb::RGB, camera::Camera, math::Ray, hit::{Hit, HitRecord}, random::random_in_range, spectrum::{self, Spectrum, WAVELENGTH_COUNT}, Background, Scatter, Scene};
 use renderer_builder::RendererBuilder;
+use light_sampler::LightSampler;
 
 /// Renders scene to some image (or buffer).
 pub struct Renderer<'a> {
@@ -9,7 +11,10 @@ pub struct Renderer<'a> {
     pub(super) camera: Camera,
     pub(super) sample_count: usize,
     pub(super) ray_depth: usize,
-    pub(super) ray_miss: Box<dyn Fn(&Ray) -> RGB + 'a + Sync>
+    pub(super) background: Box<dyn Background + 'a + Sync>,
+    pub(super) lights: LightSampler<'a>,
+    pub(super) fog: Option<(f64, Box<dyn Scatter + 'a + Sync>)>,
+    pub(super) spectral: bool
 }
 
 impl<'a> Renderer<'a> {
@@ -20,7 +25,10 @@ impl<'a> Renderer<'a> {
             camera,
             sample_count: 100,
             ray_depth: 50,
-            ray_miss: Box::new(|_| RGB::default())
+            background: Box::new(|_: &Ray| RGB::default()),
+            lights: Scene::new(),
+            fog: None,
+            spectral: false
         }
     }
 
@@ -35,7 +43,7 @@ impl<'a> Renderer<'a> {
                 for _ in 0..self.sample_count {
                     let offset = ((x as f64 + random_in_range(0.0..1.0)) / (bounds.0 as f64 - 1.0), (y as f64 + random_in_range(0.0..1.0)) / (bounds.1 as f64 - 1.0));
                     let ray = self.camera.ray_to_viewport(&offset);
-                    color += self.ray_color(&ray, self.ray_depth);
+                    color += self.sample_color(&ray);
                 }
 
                 buf[(bounds.1 - y - 1) * bounds.0 + x] = (color * scale).correct_gamma(2.0);
@@ -71,7 +79,7 @@ impl<'a> Renderer<'a> {
                             let offset_x = (pixel[0] as f64 + random_in_range(0.0..1.0) - 0.5) / (bounds.0 as f64 - 1.0);
                             let offset_y = (pixel[1] as f64 + random_in_range(0.0..1.0) - 0.5) / (bounds.1 as f64 - 1.0);
                             let ray = self.camera.ray_to_viewport(&(offset_x, offset_y));
-                            color += self.ray_color(&ray, self.ray_depth);
+                            color += self.sample_color(&ray);
                         }
 
                         chunk[index] = (color * scale).correct_gamma(2.0);
@@ -85,18 +93,131 @@ impl<'a> Renderer<'a> {
         iw.write_all(&buf);
     }
 
+    fn sample_color(&self, ray: &Ray) -> RGB {
+        if !self.spectral {
+            return self.ray_color(ray, self.ray_depth);
+        }
+
+        let wavelengths = spectrum::sample_wavelengths();
+        let radiance = self.ray_spectrum(&ray.with_wavelength(wavelengths[0]), self.ray_depth, &wavelengths, false);
+        spectrum::to_rgb(&radiance, &wavelengths)
+    }
+
     fn ray_color(&self, ray: &Ray, depth: usize) -> RGB {
         if depth == 0 {
             return RGB::default();
         }
 
-        if let Some(hit_record) = self.hittable.hit(&ray, 0.001, f64::MAX) {
-            if let Some((scattered_ray, color)) = hit_record.scatter() {
-                return color * self.ray_color(&scattered_ray, depth - 1);
+        if let Some(hit_record) = self.fog_hit(ray, self.hittable.hit(ray, 0.001, f64::MAX)) {
+            let emitted = hit_record.emitted(ray);
+            if let Some((scattered_ray, color)) = hit_record.scatter(ray) {
+                let (scattered_ray, color) = self.sample_lights(ray, &hit_record, scattered_ray, color);
+                let scattered_ray = match (scattered_ray.wavelength(), ray.wavelength()) {
+                    (None, Some(wavelength)) => scattered_ray.with_wavelength(wavelength),
+                    _ => scattered_ray
+                };
+                return emitted + color * self.ray_color(&scattered_ray, depth - 1);
             }
-            return RGB::default();
+            return emitted;
+        }
+
+        self.background.color(ray)
+    }
+
+    /// Spectral version of `ray_color` tracing `wavelengths` together, with hero wavelength carried by the ray.
+    /// Colors of materials and lights are upsampled to spectra. Wavelength-dependent scattering keeps only
+    /// the hero wavelength, which is marked by `single`.
+    fn ray_spectrum(&self, ray: &Ray, depth: usize, wavelengths: &Spectrum, single: bool) -> Spectrum {
+        if depth == 0 {
+            return [0.0; WAVELENGTH_COUNT];
+        }
+
+        let Some(hit_record) = self.fog_hit(ray, self.hittable.hit(ray, 0.001, f64::MAX)) else {
+            return spectrum::upsample(self.background.color(ray), wavelengths);
+        };
+
+        let mut radiance = spectrum::upsample(hit_record.emitted(ray), wavelengths);
+        let Some((scattered_ray, color)) = hit_record.scatter(ray) else {
+            return radiance;
+        };
+
+        let (scattered_ray, color) = self.sample_lights(ray, &hit_record, scattered_ray, color);
+        let scattered_ray = match (scattered_ray.wavelength(), ray.wavelength()) {
+            (None, Some(wavelength)) => scattered_ray.with_wavelength(wavelength),
+            _ => scattered_ray
+        };
+
+        let terminates = !single && hit_record.material().is_some_and(|material| material.is_dispersive());
+        let attenuation = spectrum::upsample(color, wavelengths);
+        let incoming = self.ray_spectrum(&scattered_ray, depth - 1, wavelengths, single || terminates);
+
+        if terminates {
+            radiance[0] += attenuation[0] * incoming[0] * WAVELENGTH_COUNT as f64;
+        } else {
+            for index in 0..WAVELENGTH_COUNT {
+                radiance[index] += attenuation[index] * incoming[index];
+            }
+        }
+
+        radiance
+    }
+
+    /// Returns scattering event in global fog if it happens before the ray reaches `hit_record`.
+    /// Rays missing all objects leave the fog unaffected.
+    fn fog_hit<'b>(&'b self, ray: &Ray, hit_record: Option<HitRecord<'b>>) -> Option<HitRecord<'b>> {
+        let (Some((density, phase)), Some(surface)) = (&self.fog, &hit_record) else {
+            return hit_record;
+        };
+
+        let t = -random_in_range(f64::EPSILON..1.0).ln() / (density * ray.direction().len());
+        if t < 0.001 || t >= surface.t() {
+            return hit_record;
+        }
+
+        let mut fog_record = HitRecord::new(t, ray.trace(t));
+        fog_record.set_face_normal(ray, -ray.direction().normalize());
+        fog_record.set_material(&**phase);
+        Some(fog_record)
+    }
+
+    /// Chooses between material sampling and sampling of lights and background, weighting result by mixture density.
+    fn sample_lights(&self, ray: &Ray, hit_record: &HitRecord, scattered_ray: Ray, color: RGB) -> (Ray, RGB) {
+        let (sample_lights, sample_background) = (!self.lights.is_empty(), self.background.is_sampled());
+        let strategy_count = sample_lights as usize + sample_background as usize;
+
+        let material = match hit_record.material() {
+            Some(material) if strategy_count > 0 => material,
+            _ => return (scattered_ray, color)
+        };
+
+        let origin = hit_record.point();
+        let strategy_probability = 0.5 / strategy_count as f64;
+        let choice = random_in_range(0.0..1.0);
+        let direction = if choice >= 0.5 {
+            scattered_ray.direction()
+        } else if sample_lights && (!sample_background || choice < strategy_probability) {
+            self.lights.random_direction(origin)
+        } else {
+            self.background.random_direction()
+        };
+
+        let Some((value, scattering_pdf)) = material.evaluate(ray, hit_record, direction) else {
+            return (scattered_ray, color);
+        };
+
+        let mut pdf = 0.5 * scattering_pdf;
+        if sample_lights {
+            pdf += strategy_probability * self.lights.pdf_value(origin, direction);
+        }
+        if sample_background {
+            pdf += strategy_probability * self.background.pdf_value(direction);
+        }
+
+        let light_ray = Ray::new(origin, direction);
+        if pdf <= 0.0 {
+            return (light_ray, RGB::default());
         }
 
-        (self.ray_miss)(&ray)
+        (light_ray, value / pdf)
     }
 }
\ No newline at end of file
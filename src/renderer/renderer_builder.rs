@@ -1,5 +1,5 @@
-use super::Renderer;
-use crate::{camera::Camera, math::Ray, rgb::RGB, Hit};
+use super::{Renderer, LightSampler};
+use crate::{camera::Camera, math::Ray, rgb::RGB, Background, Hit, Scatter, Scene};
 
 /// `RendererBuilder` builds a renderer with set parameters.
 pub struct RendererBuilder<'a> {
@@ -7,7 +7,10 @@ pub struct RendererBuilder<'a> {
     pub(super) camera: Camera,
     pub(super) sample_count: usize,
     pub(super) ray_depth: usize,
-    pub(super) ray_miss: Box<dyn Fn(&Ray) -> RGB + 'a + Sync>
+    pub(super) background: Box<dyn Background + 'a + Sync>,
+    pub(super) lights: Scene<'a>,
+    pub(super) fog: Option<(f64, Box<dyn Scatter + 'a + Sync>)>,
+    pub(super) spectral: bool
 }
 
 impl<'a> RendererBuilder<'a> {
@@ -25,7 +28,35 @@ impl<'a> RendererBuilder<'a> {
 
     /// Sets missing ray color.
     pub fn ray_miss(mut self, ray_miss: impl Fn(&Ray) -> RGB + 'a + Sync) -> Self {
-        self.ray_miss = Box::new(ray_miss);
+        self.background = Box::new(ray_miss);
+        self
+    }
+
+    /// Sets `Background` seen by missing rays, e.g. `EnvironmentMap`. Replaces color set by `ray_miss`.
+    pub fn background(mut self, background: impl Background + 'a + Sync) -> Self {
+        self.background = Box::new(background);
+        self
+    }
+
+    /// Sets objects which are sampled directly as light sources. Brighter lights (see `Hit::power`) are chosen more often.\
+    /// Objects should also be added to the rendered scene, as lights are only used to choose scattering directions.
+    pub fn lights(mut self, lights: Scene<'a>) -> Self {
+        self.lights = lights;
+        self
+    }
+
+    /// Fills space between objects with homogeneous fog of given `density`, scattering light by `phase` function
+    /// (e.g. `Isotropic` or `HenyeyGreenstein`). Rays that miss every object see the background unattenuated.
+    pub fn fog(mut self, density: f64, phase: impl Scatter + 'a + Sync) -> Self {
+        self.fog = Some((density, Box::new(phase)));
+        self
+    }
+
+    /// Enables spectral rendering. Each path traces four wavelengths (hero wavelength sampling), RGB colors of materials
+    /// and lights are converted to smooth spectra, and results are accumulated in CIE XYZ before conversion to RGB.
+    /// Dispersive materials are more accurate but images are noisier. Disabled by default.
+    pub fn spectral(mut self, spectral: bool) -> Self {
+        self.spectral = spectral;
         self
     }
 
@@ -36,7 +67,10 @@ impl<'a> RendererBuilder<'a> {
             camera: self.camera,
             sample_count: self.sample_count,
             ray_depth: self.ray_depth,
-            ray_miss: self.ray_miss
+            background: self.background,
+            lights: LightSampler::new(self.lights),
+            fog: self.fog,
+            spectral: self.spectral
         }
     }
 }
\ No newline at end of file
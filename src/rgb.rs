@@ -56,6 +56,26 @@ impl RGB {
         self.2
     }
 
+    /// Converts CIE XYZ color to linear sRGB.
+    /// ```
+    /// # use rayimg::RGB;
+    /// let white = RGB::from_xyz(0.9505, 1.0, 1.089);
+    /// assert!((white.r() - 1.0).abs() < 1e-3 && (white.g() - 1.0).abs() < 1e-3 && (white.b() - 1.0).abs() < 1e-3);
+    /// ```
+    pub fn from_xyz(x: f64, y: f64, z: f64) -> Self {
+        RGB(3.2406 * x - 1.5372 * y - 0.4986 * z, -0.9689 * x + 1.8758 * y + 0.0415 * z, 0.0557 * x - 0.2040 * y + 1.0570 * z)
+    }
+
+    /// Returns relative luminance of color (Rec. 709 primaries).
+    /// ```
+    /// # use rayimg::RGB;
+    /// assert_eq!(RGB(1.0, 1.0, 1.0).luminance(), 1.0);
+    /// assert_eq!(RGB(0.0, 0.0, 1.0).luminance(), 0.0722);
+    /// ```
+    pub fn luminance(&self) -> f64 {
+        0.2126 * self.0 + 0.7152 * self.1 + 0.0722 * self.2
+    }
+
     /// Corrects color based on gamma value, i.e. raises each component to the power 1/gamma.
     /// ```
     /// # use rayimg::RGB;
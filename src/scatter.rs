@@ -1,6 +1,30 @@
-use crate::{math::Ray, hit::HitRecord, rgb::RGB};
+use crate::{math::{Ray, Vec3}, hit::HitRecord, rgb::RGB};
 
 /// Describes material scattering properties.
 pub trait Scatter {
     fn scatter(&self, ray: &Ray, hit_record: &HitRecord) -> Option<(Ray, RGB)>;
-}
\ No newline at end of file
+
+    /// Returns light emitted from the surface towards `ray` origin. Materials emit nothing by default.
+    fn emit(&self, _ray: &Ray, _hit_record: &HitRecord) -> RGB {
+        RGB::default()
+    }
+
+    /// Returns average radiance emitted by the surface, used to estimate power of lights.
+    fn emission(&self) -> RGB {
+        RGB::default()
+    }
+
+    /// Returns true if scattering depends on wavelength of the ray (e.g. dispersion).
+    /// Spectral renderer then follows only the wavelength carried by the ray.
+    fn is_dispersive(&self) -> bool {
+        false
+    }
+
+    /// Returns BSDF multiplied by cosine of scattered `direction` and probability density (with respect to solid angle)
+    /// of `scatter` choosing that direction.\
+    /// Returns None for materials that scatter only in some discrete directions (e.g. mirrors) and thus can't be
+    /// combined with light sampling.
+    fn evaluate(&self, _ray: &Ray, _hit_record: &HitRecord, _direction: Vec3<f64>) -> Option<(RGB, f64)> {
+        None
+    }
+}
@@ -1,8 +1,9 @@
-use crate::{hit::{Hit, HitRecord}, math::Ray, AABB};
+use crate::{hit::{Hit, HitRecord}, math::{Ray, Vec3}, random::random_in_range, AABB};
 
 use std::sync::Arc;
 
-/// Scene contains information about hittable objects. It's also hittable.
+/// Scene contains information about hittable objects. It's also hittable.\
+/// Unbounded objects (e.g. `Plane`) make bounding of the whole scene infinite.
 /// ```
 /// use rayimg::{Scene, shapes::Sphere, materials::Lambertian, math::{Vec3, Ray}, RGB, Hit, HitRecord};
 ///
@@ -41,9 +42,8 @@ impl<'a> Scene<'a> {
     /// ```
     pub fn add_object(&mut self, object: impl Hit + 'a + Send + Sync) {
         self.objects.push(Arc::new(object));
-        if let Some(aabb) = self.aabb {
-            self.aabb = Some(AABB::unite(aabb, self.objects.last().unwrap().bounding()))
-        }
+        let bounding = self.objects.last().unwrap().bounding();
+        self.aabb = Some(self.aabb.map_or(bounding, |aabb| AABB::unite(aabb, bounding)));
     }
 
     /// Returns count of objects
@@ -66,7 +66,7 @@ impl<'a> Scene<'a> {
 }
 
 impl<'a> Hit for Scene<'a> {
-    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitRecord<'_>> {
         let mut hit_record = None;
         for object in &self.objects {
             if let Some(temp_hit_record) = object.hit(ray, t_min, t_max) {
@@ -83,4 +83,25 @@ impl<'a> Hit for Scene<'a> {
     fn bounding(&self) -> AABB {
         self.aabb.unwrap_or_default()
     }
+
+    fn power(&self) -> f64 {
+        self.objects.iter().map(|object| object.power()).sum()
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if self.objects.is_empty() {
+            return 0.0;
+        }
+
+        let sum = self.objects.iter().map(|object| object.pdf_value(origin, direction)).sum::<f64>();
+        sum / self.objects.len() as f64
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        if self.objects.is_empty() {
+            return Vec3::new(1.0, 0.0, 0.0);
+        }
+
+        self.objects[random_in_range(0..self.objects.len())].random_direction(origin)
+    }
 }
@@ -0,0 +1,83 @@
+use crate::{hit::{Hit, HitRecord}, math::{Ray, Vec3}, random::random_in_range, texture::Texture, AABB};
+
+use std::sync::Arc;
+
+/// Cuts holes into `shape` by opacity texture, e.g. leaves or chain-link fence drawn on triangles.\
+/// Opacity is luminance of the texture at hit coordinates. Hits with opacity below `threshold` are rejected,
+/// and partially opaque hits are passed through with probability `1.0 - opacity`. Rejected hits don't stop the ray,
+/// so surfaces behind the hole are found.\
+/// `AlphaMask` must not wrap lights: power and sampled directions are those of the whole unmasked shape,
+/// so masked emitters added to renderer `lights` get too much weight and waste samples on the holes.
+/// ```
+/// use rayimg::{shapes::{AlphaMask, Triangle}, materials::Lambertian, math::{Ray, Vec3}, Hit, RGB};
+///
+/// let triangle = Triangle::new([Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)], Lambertian::new(RGB::default()));
+/// let hidden = AlphaMask::new(triangle, 0.2).threshold(0.5);
+/// assert!(hidden.hit(&Ray::new(Vec3::new(0.2, 0.2, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).is_none());
+/// ```
+pub struct AlphaMask<'a> {
+    shape: Box<dyn Hit + 'a + Send + Sync>,
+    opacity: Arc<dyn Texture + 'a + Send + Sync>,
+    threshold: f64
+}
+
+impl<'a> AlphaMask<'a> {
+    /// Creates new `AlphaMask` with threshold 0.0, so only fully transparent hits are always rejected.
+    pub fn new(shape: impl Hit + 'a + Send + Sync, opacity: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            shape: Box::new(shape),
+            opacity: Arc::new(opacity),
+            threshold: 0.0
+        }
+    }
+
+    /// Sets opacity below which hits are always rejected.
+    pub fn threshold(mut self, threshold: f64) -> Self {
+        self.threshold = threshold;
+        self
+    }
+
+    fn is_opaque(&self, hit_record: &HitRecord) -> bool {
+        let (u, v) = hit_record.uv();
+        let opacity = self.opacity.value(u, v, hit_record.point()).luminance();
+
+        if opacity >= 1.0 {
+            true
+        } else if opacity <= 0.0 || opacity < self.threshold {
+            false
+        } else {
+            random_in_range(0.0..1.0) < opacity
+        }
+    }
+}
+
+impl<'a> Hit for AlphaMask<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let mut t_min = t_min;
+        loop {
+            let hit_record = self.shape.hit(ray, t_min, t_max)?;
+            if self.is_opaque(&hit_record) {
+                return Some(hit_record);
+            }
+
+            // Continue behind the rejected hit, shape may still be hit further along the ray.
+            t_min = hit_record.t().next_up();
+        }
+    }
+
+    fn bounding(&self) -> AABB {
+        self.shape.bounding()
+    }
+
+    fn power(&self) -> f64 {
+        self.shape.power()
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        self.shape.pdf_value(origin, direction)
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        self.shape.random_direction(origin)
+    }
+}
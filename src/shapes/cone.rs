@@ -0,0 +1,110 @@
+use crate::{hit::{successive_hits, Hit, HitRecord, Solid}, math::{Ray, Vec3}, scatter::Scatter, AABB};
+use super::revolution::{azimuth, azimuth_tangent, closer, Cap, solve_quadratic, LocalHit};
+
+use std::{f64::consts::PI, sync::Arc};
+
+/// Cone with circular bottom of `radius` at `base` and apex at `height` above it along `y` axis.\
+/// Optionally closed by bottom cap or cut to partial sweep angle. Other orientations can be set by `Transformed`.\
+/// Texture coordinates of the side are angle around the axis (divided by sweep) and height (divided by `height`).
+/// ```
+/// # use rayimg::{shapes::Cone, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+/// let cone = Cone::new(Vec3::new(0.0, 0.0, 0.0), 1.0, 1.0, Lambertian::new(RGB::default()));
+/// let hit_record = cone.hit(&Ray::new(Vec3::new(3.0, 0.5, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert_eq!(hit_record.point(), Vec3::new(0.5, 0.5, 0.0));
+/// assert!((hit_record.normal() - Vec3::new(1.0, 1.0, 0.0).normalize()).len() < 1e-9);
+/// ```
+#[derive(Clone)]
+pub struct Cone<'a> {
+    base: Vec3<f64>,
+    radius: f64,
+    height: f64,
+    phi_max: f64,
+    capped: bool,
+    aabb: AABB,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Cone<'a> {
+    /// Creates new open `Cone`.
+    pub fn new(base: Vec3<f64>, radius: f64, height: f64, material: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            base,
+            radius,
+            height,
+            phi_max: 2.0 * PI,
+            capped: false,
+            aabb: AABB::from_two_points(base - Vec3::new(radius, 0.0, radius), base + Vec3::new(radius, height, radius)).pad(1e-4),
+            material: Arc::new(material)
+        }
+    }
+
+    /// Closes `Cone` by flat cap at the bottom.
+    pub fn capped(mut self, capped: bool) -> Self {
+        self.capped = capped;
+        self
+    }
+
+    /// Cuts `Cone` to sweep angle in degrees around the axis, starting from `+x`. Default is 360.
+    pub fn sweep(mut self, degrees: f64) -> Self {
+        self.phi_max = degrees.clamp(0.0, 360.0).to_radians();
+        self
+    }
+
+    fn side(&self, origin: Vec3<f64>, direction: Vec3<f64>, t_min: f64, t_max: f64) -> Option<LocalHit> {
+        // Radius at height `y` is `k (height - y)`.
+        let k = self.radius / self.height;
+        let k2 = k * k;
+        let rest = self.height - origin.y;
+
+        let a = direction.x * direction.x + direction.z * direction.z - k2 * direction.y * direction.y;
+        let b = 2.0 * (origin.x * direction.x + origin.z * direction.z + k2 * rest * direction.y);
+        let c = origin.x * origin.x + origin.z * origin.z - k2 * rest * rest;
+
+        let (t0, t1) = solve_quadratic(a, b, c)?;
+        [t0, t1].into_iter().filter(|t| t_min <= *t && *t <= t_max).find_map(|t| {
+            let point = origin + direction * t;
+            let phi = azimuth(point);
+            if point.y < 0.0 || point.y > self.height || phi > self.phi_max {
+                return None;
+            }
+
+            let apex = Vec3::new(0.0, self.height, 0.0);
+            Some(LocalHit {
+                t,
+                point,
+                outward: Vec3::new(point.x, k2 * (self.height - point.y), point.z),
+                uv: (phi / self.phi_max, point.y / self.height),
+                tangents: (azimuth_tangent(point) * self.phi_max, (apex - point) / (1.0 - point.y / self.height).max(1e-9))
+            })
+        })
+    }
+}
+
+impl<'a> Hit for Cone<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let (origin, direction) = (ray.origin() - self.base, ray.direction());
+
+        let mut closest = self.side(origin, direction, t_min, t_max);
+        if self.capped {
+            let t_max = closest.as_ref().map_or(t_max, |hit| hit.t);
+            closest = closer(closest, Cap { y: 0.0, radius: self.radius, phi_max: self.phi_max, up: false }.hit(origin, direction, t_min, t_max));
+        }
+
+        closest.map(|hit| hit.record(ray, self.base, &*self.material))
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+}
+
+/// Open `Cone` (not capped or cut by sweep) has no inside, so it gives no crossings and `Csg` treats it as empty.
+impl<'a> Solid for Cone<'a> {
+    fn crossings(&self, ray: &Ray, t_min: f64) -> Vec<HitRecord<'_>> {
+        if self.capped && self.phi_max >= 2.0 * PI {
+            successive_hits(self, ray, t_min)
+        } else {
+            Vec::new()
+        }
+    }
+}
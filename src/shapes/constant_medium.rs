@@ -0,0 +1,62 @@
+use crate::{hit::{Hit, HitRecord}, math::Ray, random::random_in_range, scatter::Scatter, AABB};
+
+use std::sync::Arc;
+
+/// Volume of constant density filling closed `boundary` shape, e.g. smoke, fog or murky water.\
+/// Rays passing through the volume are scattered at random distances according to `density`,
+/// and new directions are chosen by `phase` function (`Isotropic` or `HenyeyGreenstein` material).
+/// ```
+/// use rayimg::{shapes::{ConstantMedium, Sphere}, materials::{Isotropic, Lambertian}, math::{Ray, Vec3}, Hit, RGB};
+///
+/// let smoke = ConstantMedium::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default())), 1000.0, Isotropic::new(RGB(0.5, 0.5, 0.5)));
+/// let hit_record = smoke.hit(&Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+/// assert!(hit_record.t() > 4.0 && hit_record.t() < 4.1);
+/// ```
+pub struct ConstantMedium<'a> {
+    boundary: Box<dyn Hit + 'a + Send + Sync>,
+    negative_inverse_density: f64,
+    phase: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> ConstantMedium<'a> {
+    /// Creates new `ConstantMedium` inside `boundary`. Material of boundary is not used.
+    pub fn new(boundary: impl Hit + 'a + Send + Sync, density: f64, phase: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            boundary: Box::new(boundary),
+            negative_inverse_density: -1.0 / density,
+            phase: Arc::new(phase)
+        }
+    }
+}
+
+impl<'a> Hit for ConstantMedium<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let entry = self.boundary.hit(ray, f64::MIN, f64::MAX)?.t();
+        let exit = self.boundary.hit(ray, entry + 1e-4, f64::MAX)?.t();
+
+        let (entry, exit) = (entry.max(t_min).max(0.0), exit.min(t_max));
+        if entry >= exit {
+            return None;
+        }
+
+        let ray_length = ray.direction().len();
+        let distance_inside = (exit - entry) * ray_length;
+        let hit_distance = self.negative_inverse_density * random_in_range(f64::EPSILON..1.0).ln();
+        if hit_distance > distance_inside {
+            return None;
+        }
+
+        let t = entry + hit_distance / ray_length;
+        let mut hit_record = HitRecord::new(t, ray.trace(t));
+
+        // Particles have no surface, so normal just faces the ray.
+        hit_record.set_face_normal(ray, -ray.direction().normalize());
+        hit_record.set_material(&*self.phase);
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.boundary.bounding()
+    }
+}
@@ -0,0 +1,127 @@
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Vec3}, Interval, AABB};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Operation {
+    Union,
+    Intersection,
+    Difference
+}
+
+impl Operation {
+    fn contains(&self, first: bool, second: bool) -> bool {
+        match self {
+            Operation::Union => first || second,
+            Operation::Intersection => first && second,
+            Operation::Difference => first && !second
+        }
+    }
+}
+
+/// Constructive solid geometry, union, intersection or difference of two `Solid`s.\
+/// Surface of the result keeps materials of the shapes it's made of, and can be combined further.
+/// ```
+/// use rayimg::{shapes::{Csg, Sphere, Cuboid}, materials::Lambertian, math::{Ray, Vec3}, Hit, RGB};
+///
+/// let cube = Cuboid::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0), Lambertian::new(RGB::default()));
+/// let sphere = Sphere::new(Vec3::new(0.0, 0.0, 1.0), 0.5, Lambertian::new(RGB::default()));
+/// let carved = Csg::difference(cube, sphere);
+///
+/// // Ray goes into the hole, hitting inner side of the sphere.
+/// let hit_record = carved.hit(&Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+/// assert_eq!(hit_record.point(), Vec3::new(0.0, 0.0, 0.5));
+/// assert_eq!(hit_record.normal(), Vec3::new(0.0, 0.0, 1.0));
+/// assert!(hit_record.front_face());
+/// ```
+pub struct Csg<A, B> {
+    first: A,
+    second: B,
+    operation: Operation,
+    aabb: AABB
+}
+
+impl<A: Solid, B: Solid> Csg<A, B> {
+    /// Creates union, points inside either of the shapes.
+    pub fn union(first: A, second: B) -> Self {
+        let aabb = AABB::unite(first.bounding(), second.bounding());
+        Self::new(first, second, Operation::Union, aabb)
+    }
+
+    /// Creates intersection, points inside both shapes.
+    pub fn intersection(first: A, second: B) -> Self {
+        let (a, b) = (first.bounding(), second.bounding());
+        let aabb = AABB::with_intervals([0, 1, 2].map(|axis| Interval::limits(a.axes[axis].min.max(b.axes[axis].min), a.axes[axis].max.min(b.axes[axis].max))));
+        Self::new(first, second, Operation::Intersection, aabb)
+    }
+
+    /// Creates difference, points inside the first shape but not inside the second.
+    pub fn difference(first: A, second: B) -> Self {
+        let aabb = first.bounding();
+        Self::new(first, second, Operation::Difference, aabb)
+    }
+
+    fn new(first: A, second: B, operation: Operation, aabb: AABB) -> Self {
+        Self {
+            first,
+            second,
+            operation,
+            aabb
+        }
+    }
+}
+
+impl<A: Solid, B: Solid> Hit for Csg<A, B> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        self.aabb.clip(ray, t_min, t_max)?;
+        self.crossings(ray, t_min).into_iter().next().filter(|hit_record| hit_record.t() <= t_max)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+}
+
+impl<A: Solid, B: Solid> Solid for Csg<A, B> {
+    fn crossings(&self, ray: &Ray, t_min: f64) -> Vec<HitRecord<'_>> {
+        if self.aabb.clip(ray, t_min, f64::INFINITY).is_none() {
+            return Vec::new();
+        }
+
+        let (first, second) = (self.first.crossings(ray, t_min), self.second.crossings(ray, t_min));
+
+        // Ray starts inside when the first crossing leaves the shape.
+        let mut inside = [&first, &second].map(|crossings| crossings.first().is_some_and(|hit_record| !hit_record.front_face()));
+        let mut was_inside = self.operation.contains(inside[0], inside[1]);
+
+        let mut crossings = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < first.len() || j < second.len() {
+            let (hit_record, shape) = if j == second.len() || (i < first.len() && first[i].t() <= second[j].t()) {
+                i += 1;
+                (first[i - 1], 0)
+            } else {
+                j += 1;
+                (second[j - 1], 1)
+            };
+
+            inside[shape] = hit_record.front_face();
+            let is_inside = self.operation.contains(inside[0], inside[1]);
+            if is_inside != was_inside {
+                // Entering the result must be front face, e.g. surface of subtracted shape faces into it.
+                crossings.push(if hit_record.front_face() == is_inside { hit_record } else { flip(hit_record, ray) });
+                was_inside = is_inside;
+            }
+        }
+
+        crossings
+    }
+}
+
+/// Turns surface of `HitRecord` inside out.
+fn flip<'a>(hit_record: HitRecord<'a>, ray: &Ray) -> HitRecord<'a> {
+    let outward = |normal: Vec3<f64>| if hit_record.front_face() { normal } else { -normal };
+
+    let mut flipped = hit_record;
+    flipped.set_face_normal(ray, -outward(hit_record.geometric_normal()));
+    flipped.set_shading_normal(-outward(hit_record.normal()));
+    flipped
+}
@@ -0,0 +1,96 @@
+use crate::{math::{Ray, Vec3}, random::random_in_range, Hit, HitRecord, Scatter, Solid, AABB};
+use super::Quad;
+
+use std::sync::Arc;
+
+/// Axis-aligned box between two corner points, made of six outward-facing `Quad`s.\
+/// Can be rotated by `Transformed`.
+/// ```
+/// # use rayimg::{shapes::Cuboid, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+/// let cuboid = Cuboid::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 2.0, 1.0), Lambertian::new(RGB::default()));
+/// let hit_record = cuboid.hit(&Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert_eq!(hit_record.point(), Vec3::new(0.0, 2.0, 0.0));
+/// assert_eq!(hit_record.normal(), Vec3::new(0.0, 1.0, 0.0));
+/// assert_eq!(cuboid.area(), 32.0);
+/// ```
+#[derive(Clone)]
+pub struct Cuboid<'a> {
+    faces: [Quad<'a>; 6],
+    area: f64,
+    aabb: AABB
+}
+
+impl<'a> Cuboid<'a> {
+    /// Creates new `Cuboid` from two opposite corners.
+    pub fn new(a: Vec3<f64>, b: Vec3<f64>, material: impl Scatter + 'a + Send + Sync) -> Self {
+        let material: Arc<dyn Scatter + 'a + Send + Sync> = Arc::new(material);
+        let min = Vec3::new(a.x.min(b.x), a.y.min(b.y), a.z.min(b.z));
+        let max = Vec3::new(a.x.max(b.x), a.y.max(b.y), a.z.max(b.z));
+
+        let dx = Vec3::new(max.x - min.x, 0.0, 0.0);
+        let dy = Vec3::new(0.0, max.y - min.y, 0.0);
+        let dz = Vec3::new(0.0, 0.0, max.z - min.z);
+
+        let face = |corner, u, v| Quad::with_material(corner, u, v, material.clone());
+        let faces = [
+            face(Vec3::new(min.x, min.y, max.z), dx, dy),
+            face(Vec3::new(max.x, min.y, min.z), -dx, dy),
+            face(Vec3::new(max.x, min.y, max.z), -dz, dy),
+            face(min, dz, dy),
+            face(Vec3::new(min.x, max.y, max.z), dx, -dz),
+            face(min, dx, dz)
+        ];
+
+        Self {
+            area: faces.iter().map(Quad::area).sum(),
+            faces,
+            aabb: AABB::from_two_points(min, max).pad(1e-4)
+        }
+    }
+
+    /// Returns surface area of `Cuboid`.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+}
+
+impl<'a> Hit for Cuboid<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, mut t_max: f64) -> Option<HitRecord<'_>> {
+        let mut closest = None;
+        for face in &self.faces {
+            if let Some(hit_record) = face.hit(ray, t_min, t_max) {
+                t_max = hit_record.t();
+                closest = Some(hit_record);
+            }
+        }
+
+        closest
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+
+    fn power(&self) -> f64 {
+        self.faces.iter().map(Hit::power).sum()
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        // Faces are sampled proportionally to their area.
+        self.faces.iter().map(|face| face.area() / self.area * face.pdf_value(origin, direction)).sum()
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        let mut target = random_in_range(0.0..self.area);
+        for face in &self.faces {
+            if target < face.area() {
+                return face.random_direction(origin);
+            }
+            target -= face.area();
+        }
+
+        self.faces[5].random_direction(origin)
+    }
+}
+
+impl<'a> Solid for Cuboid<'a> {}
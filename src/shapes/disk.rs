@@ -0,0 +1,109 @@
+use crate::{math::{Ray, Vec3, ONB}, Hit, HitRecord, Scatter, AABB};
+
+use std::{f64::consts::PI, sync::Arc};
+
+/// Flat circle of `radius` around `center`, facing along `normal`.\
+/// Texture coordinates are polar: `u` is angle around the normal divided by `2π`, `v` is distance from the center divided by radius.
+#[derive(Clone)]
+pub struct Disk<'a> {
+    center: Vec3<f64>,
+    radius: f64,
+    onb: ONB,
+    aabb: AABB,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Disk<'a> {
+    /// Creates new `Disk`.
+    /// ```
+    /// # use rayimg::{shapes::Disk, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+    /// let disk = Disk::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), 2.0, Lambertian::new(RGB::default()));
+    /// let hit_record = disk.hit(&Ray::new(Vec3::new(1.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    /// assert_eq!(hit_record.point(), Vec3::new(1.0, 0.0, 0.0));
+    /// assert_eq!(hit_record.uv().1, 0.5);
+    /// assert!(disk.hit(&Ray::new(Vec3::new(1.5, 1.0, 1.5), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).is_none());
+    /// ```
+    pub fn new(center: Vec3<f64>, normal: Vec3<f64>, radius: f64, material: impl Scatter + 'a + Send + Sync) -> Self {
+        let normal = normal.normalize();
+        let extent = Vec3::new(
+            radius * (1.0 - normal.x * normal.x).max(0.0).sqrt(),
+            radius * (1.0 - normal.y * normal.y).max(0.0).sqrt(),
+            radius * (1.0 - normal.z * normal.z).max(0.0).sqrt()
+        );
+
+        Self {
+            center,
+            radius,
+            onb: ONB::new(normal),
+            aabb: AABB::from_two_points(center - extent, center + extent).pad(1e-4),
+            material: Arc::new(material)
+        }
+    }
+
+    /// Returns area of `Disk`.
+    pub fn area(&self) -> f64 {
+        PI * self.radius * self.radius
+    }
+}
+
+impl<'a> Hit for Disk<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let normal = self.onb.w();
+        let denominator = normal.dot(&ray.direction());
+        if denominator.abs() < 1e-12 {
+            return None;
+        }
+
+        let t = normal.dot(&(self.center - ray.origin())) / denominator;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        let point = ray.trace(t);
+        let local = self.onb.project(point - self.center);
+        let r = (local.x * local.x + local.y * local.y).sqrt();
+        if r > self.radius {
+            return None;
+        }
+
+        let mut hit_record = HitRecord::new(t, point);
+        hit_record.set_face_normal(ray, normal);
+
+        let phi = local.y.atan2(local.x).rem_euclid(2.0 * PI);
+        hit_record.set_uv(phi / (2.0 * PI), r / self.radius);
+        if r > 1e-9 {
+            hit_record.set_tangents(
+                self.onb.local(Vec3::new(-local.y, local.x, 0.0)) * (2.0 * PI),
+                self.onb.local(Vec3::new(local.x, local.y, 0.0)) * (self.radius / r)
+            );
+        } else {
+            // Angle is undefined at the center.
+            hit_record.set_tangents(self.onb.u(), self.onb.v());
+        }
+        hit_record.set_material(&*self.material);
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+
+    fn power(&self) -> f64 {
+        self.material.emission().luminance() * self.area() * PI
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if let Some(hit_record) = self.hit(&Ray::new(origin, direction), 0.001, f64::MAX) {
+            let distance_squared = hit_record.t() * hit_record.t() * direction.squared_magnitude();
+            let cosine = direction.dot(&self.onb.w()).abs() / direction.len();
+            distance_squared / (cosine * self.area())
+        } else {
+            0.0
+        }
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        self.center + self.onb.local(Vec3::random_in_unit_disk() * self.radius) - origin
+    }
+}
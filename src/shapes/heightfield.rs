@@ -0,0 +1,266 @@
+use crate::{hit::{Hit, HitRecord}, image_read::read_image, math::{Ray, Vec3}, scatter::Scatter, AABB};
+
+use std::{io, path::Path, sync::Arc};
+
+/// Grid vertices of triangle with barycentric weights of hit point.
+type Weights = [((usize, usize), f64); 3];
+
+/// Terrain surface of heights sampled on a regular grid, e.g. from DEM or heightmap image.\
+/// Grid spans `x` and `z` from 0 to 1 with heights along `y` as they are, unless changed by `scale` and `offset`.
+/// Each cell is split into two triangles with interpolated normals, and ray visits only cells under it (grid traversal),
+/// skipping cells whose height range it passes above or below.\
+/// Texture coordinates span the whole grid from `(0, 0)` to `(1, 1)`.
+/// ```
+/// use rayimg::{shapes::Heightfield, materials::Lambertian, math::{Ray, Vec3}, Hit, RGB};
+///
+/// // Ramp rising along x.
+/// let ramp = Heightfield::new((3, 2), vec![0.0, 0.5, 1.0, 0.0, 0.5, 1.0], Lambertian::new(RGB::default()))
+///     .scale(Vec3::new(4.0, 2.0, 4.0));
+///
+/// let hit_record = ramp.hit(&Ray::new(Vec3::new(1.0, 5.0, 2.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert!((hit_record.point() - Vec3::new(1.0, 0.5, 2.0)).len() < 1e-9);
+/// assert!((hit_record.normal() - Vec3::new(-1.0, 2.0, 0.0).normalize()).len() < 1e-9);
+/// assert_eq!(hit_record.uv(), (0.25, 0.5));
+/// ```
+#[derive(Clone)]
+pub struct Heightfield<'a> {
+    resolution: (usize, usize),
+    heights: Vec<f64>,
+    gradients: Vec<(f64, f64)>,
+    ranges: Vec<(f64, f64)>,
+    scale: Vec3<f64>,
+    offset: Vec3<f64>,
+    aabb: AABB,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Heightfield<'a> {
+    /// Creates new `Heightfield` from `heights` of grid with resolution along `x` and `z`, ordered with `x` changing fastest.
+    pub fn new(resolution: (usize, usize), heights: Vec<f64>, material: impl Scatter + 'a + Send + Sync) -> Self {
+        assert!(resolution.0 > 1 && resolution.1 > 1, "Heightfield needs at least 2x2 samples");
+        assert_eq!(heights.len(), resolution.0 * resolution.1, "Height count does not match heightfield resolution");
+
+        let mut heightfield = Self {
+            resolution,
+            heights,
+            gradients: Vec::new(),
+            ranges: Vec::new(),
+            scale: Vec3::new(1.0, 1.0, 1.0),
+            offset: Vec3::default(),
+            aabb: AABB::default(),
+            material: Arc::new(material)
+        };
+
+        heightfield.gradients = heightfield.compute_gradients();
+        heightfield.ranges = heightfield.compute_ranges();
+        heightfield.aabb = heightfield.compute_aabb();
+        heightfield
+    }
+
+    /// Loads `Heightfield` from `.pgm` or `.png` image, where brightness of pixels (kept linear, 8 or 16 bits) is height.
+    /// Rows of the image go along `z`, columns along `x`.
+    pub fn load(path: impl AsRef<Path>, material: impl Scatter + 'a + Send + Sync) -> io::Result<Self> {
+        let image = read_image(path.as_ref())?;
+        if image.width < 2 || image.height < 2 {
+            return Err(io::Error::new(io::ErrorKind::InvalidData, "heightmap needs at least 2x2 pixels"));
+        }
+
+        Ok(Self::new((image.width, image.height), image.pixels.iter().map(|pixel| pixel.luminance()).collect(), material))
+    }
+
+    /// Sets size of the grid along `x` and `z`, and height of 1.0 along `y`. Default is 1.0 for all.\
+    /// All components must be positive, mirrored terrain can be made by `Transformed`.
+    pub fn scale(mut self, scale: Vec3<f64>) -> Self {
+        assert!(scale.x > 0.0 && scale.y > 0.0 && scale.z > 0.0, "Heightfield scale must be positive");
+        self.scale = scale;
+        self.aabb = self.compute_aabb();
+        self
+    }
+
+    /// Moves corner of the grid at zero height from the origin to `offset`.
+    pub fn offset(mut self, offset: Vec3<f64>) -> Self {
+        self.offset = offset;
+        self.aabb = self.compute_aabb();
+        self
+    }
+
+    fn height(&self, x: usize, z: usize) -> f64 {
+        self.heights[z * self.resolution.0 + x]
+    }
+
+    /// Returns size of a cell along `x` and `z`.
+    fn cell_size(&self) -> (f64, f64) {
+        (self.scale.x / (self.resolution.0 - 1) as f64, self.scale.z / (self.resolution.1 - 1) as f64)
+    }
+
+    /// Central differences of heights per grid step at samples, one-sided at the borders.
+    fn compute_gradients(&self) -> Vec<(f64, f64)> {
+        let (nx, nz) = self.resolution;
+        let difference = |low: f64, high: f64, steps: usize| (high - low) / steps as f64;
+
+        (0..nz).flat_map(|z| (0..nx).map(move |x| (x, z))).map(|(x, z)| {
+            let (left, right) = (x.saturating_sub(1), (x + 1).min(nx - 1));
+            let (back, front) = (z.saturating_sub(1), (z + 1).min(nz - 1));
+            (
+                difference(self.height(left, z), self.height(right, z), right - left),
+                difference(self.height(x, back), self.height(x, front), front - back)
+            )
+        }).collect()
+    }
+
+    /// Minimum and maximum height of every cell.
+    fn compute_ranges(&self) -> Vec<(f64, f64)> {
+        let (nx, nz) = self.resolution;
+        (0..nz - 1).flat_map(|z| (0..nx - 1).map(move |x| (x, z))).map(|(x, z)| {
+            let corners = [self.height(x, z), self.height(x + 1, z), self.height(x, z + 1), self.height(x + 1, z + 1)];
+            (corners.iter().cloned().fold(f64::INFINITY, f64::min), corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max))
+        }).collect()
+    }
+
+    fn compute_aabb(&self) -> AABB {
+        let (min, max) = self.ranges.iter().fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), range| (min.min(range.0), max.max(range.1)));
+        AABB::from_two_points(
+            self.offset + Vec3::new(0.0, min * self.scale.y, 0.0),
+            self.offset + Vec3::new(self.scale.x, max * self.scale.y, self.scale.z)
+        ).pad(1e-4)
+    }
+
+    /// Intersects ray in grid space with triangles of cell, returning `t` and barycentric coordinates of the closest hit.
+    fn hit_cell(&self, origin: Vec3<f64>, direction: Vec3<f64>, cell: (usize, usize), t_min: f64, t_max: f64) -> Option<(f64, Weights)> {
+        let (x, z) = cell;
+        let vertex = |x: usize, z: usize| Vec3::new(x as f64, self.height(x, z), z as f64);
+        let triangles = [[(x, z), (x + 1, z + 1), (x + 1, z)], [(x, z), (x, z + 1), (x + 1, z + 1)]];
+
+        let mut closest: Option<(f64, Weights)> = None;
+        for corners in triangles {
+            let [a, b, c] = corners.map(|(x, z)| vertex(x, z));
+            let (edge1, edge2) = (b - a, c - a);
+
+            let h = direction.cross(&edge2);
+            let determinant = edge1.dot(&h);
+            if determinant.abs() < 1e-12 {
+                continue;
+            }
+
+            let s = origin - a;
+            let u = s.dot(&h) / determinant;
+            let q = s.cross(&edge1);
+            let v = direction.dot(&q) / determinant;
+            if u < 0.0 || v < 0.0 || u + v > 1.0 {
+                continue;
+            }
+
+            let t = edge2.dot(&q) / determinant;
+            if t < t_min || t_max < t || closest.is_some_and(|(closest_t, _)| closest_t <= t) {
+                continue;
+            }
+
+            closest = Some((t, [(corners[0], 1.0 - u - v), (corners[1], u), (corners[2], v)]));
+        }
+
+        closest
+    }
+
+    /// Creates `HitRecord` from barycentric weights of triangle corners.
+    fn record(&self, ray: &Ray, t: f64, weights: Weights) -> HitRecord<'_> {
+        let (nx, nz) = self.resolution;
+        let (cell_x, cell_z) = self.cell_size();
+
+        let (mut x, mut z, mut gradient) = (0.0, 0.0, (0.0, 0.0));
+        for ((corner_x, corner_z), weight) in weights {
+            let corner_gradient = self.gradients[corner_z * nx + corner_x];
+            x += corner_x as f64 * weight;
+            z += corner_z as f64 * weight;
+            gradient = (gradient.0 + corner_gradient.0 * weight, gradient.1 + corner_gradient.1 * weight);
+        }
+
+        // Slopes of the flat triangle per grid step.
+        let [(a, _), (b, _), (c, _)] = weights;
+        let height = |(x, z): (usize, usize)| self.height(x, z);
+        let (slope_x, slope_z) = if a.0 == b.0 {
+            (height(c) - height(b), height(b) - height(a))
+        } else {
+            (height(c) - height(a), height(b) - height(c))
+        };
+
+        let normal = |(slope_x, slope_z): (f64, f64)| Vec3::new(-slope_x * self.scale.y / cell_x, 1.0, -slope_z * self.scale.y / cell_z).normalize();
+
+        let mut hit_record = HitRecord::new(t, ray.trace(t));
+        hit_record.set_face_normal(ray, normal((slope_x, slope_z)));
+        hit_record.set_shading_normal(normal(gradient));
+        hit_record.set_uv(x / (nx - 1) as f64, z / (nz - 1) as f64);
+        hit_record.set_tangents(
+            Vec3::new(self.scale.x, slope_x * self.scale.y * (nx - 1) as f64, 0.0),
+            Vec3::new(0.0, slope_z * self.scale.y * (nz - 1) as f64, self.scale.z)
+        );
+        hit_record.set_material(&*self.material);
+        hit_record
+    }
+}
+
+impl<'a> Hit for Heightfield<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let (t_enter, t_exit) = self.aabb.clip(ray, t_min, t_max)?;
+
+        // Grid space has unit cells and unscaled heights, `t` stays the same.
+        let (nx, nz) = self.resolution;
+        let (cell_x, cell_z) = self.cell_size();
+        let to_grid = |v: Vec3<f64>| Vec3::new(v.x / cell_x, v.y / self.scale.y, v.z / cell_z);
+        let (origin, direction) = (to_grid(ray.origin() - self.offset), to_grid(ray.direction()));
+
+        let start = origin + direction * t_enter;
+        let mut cell = [start.x.floor().clamp(0.0, (nx - 2) as f64) as usize, start.z.floor().clamp(0.0, (nz - 2) as f64) as usize];
+        let limits = [nx - 1, nz - 1];
+        let (position, step) = ([origin.x, origin.z], [direction.x, direction.z]);
+
+        // Parameter `t` at which ray crosses next cell boundary along each axis.
+        let mut t_next = [0.0; 2];
+        for axis in 0..2 {
+            t_next[axis] = if step[axis] > 0.0 {
+                ((cell[axis] + 1) as f64 - position[axis]) / step[axis]
+            } else if step[axis] < 0.0 {
+                (cell[axis] as f64 - position[axis]) / step[axis]
+            } else {
+                f64::INFINITY
+            };
+        }
+
+        let mut t_cell = t_enter;
+        loop {
+            let t_leave = t_next[0].min(t_next[1]).min(t_exit);
+
+            // Height range of the ray over the cell must overlap heights of the cell.
+            let (y_enter, y_leave) = (origin.y + direction.y * t_cell, origin.y + direction.y * t_leave);
+            let (low, high) = self.ranges[cell[1] * (nx - 1) + cell[0]];
+            if y_enter.min(y_leave) <= high + 1e-9 && y_enter.max(y_leave) >= low - 1e-9 {
+                if let Some((t, weights)) = self.hit_cell(origin, direction, (cell[0], cell[1]), t_min, t_max) {
+                    return Some(self.record(ray, t, weights));
+                }
+            }
+
+            if t_leave >= t_exit {
+                return None;
+            }
+
+            let axis = if t_next[0] < t_next[1] { 0 } else { 1 };
+            if step[axis] > 0.0 {
+                if cell[axis] + 1 >= limits[axis] {
+                    return None;
+                }
+                cell[axis] += 1;
+            } else {
+                if cell[axis] == 0 {
+                    return None;
+                }
+                cell[axis] -= 1;
+            }
+
+            t_cell = t_leave;
+            t_next[axis] += 1.0 / step[axis].abs();
+        }
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+}
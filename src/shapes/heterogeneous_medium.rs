@@ -0,0 +1,104 @@
+use crate::{hit::{Hit, HitRecord}, math::Ray, random::random_in_range, scatter::Scatter, texture::Texture, AABB};
+
+use std::sync::Arc;
+
+/// Volume with density varying in space inside `bounds`, e.g. clouds or explosions.\
+/// Density at a point is luminance of `density` texture (usually `VoxelGrid` or `Noise`) multiplied by `scale`.
+/// Scattering distances are sampled with delta tracking against constant `majorant`, which must not be less than
+/// any density in the volume (densities above it are clamped).
+/// ```
+/// use rayimg::{shapes::HeterogeneousMedium, textures::VoxelGrid, materials::Isotropic, math::{Ray, Vec3}, RGB, AABB};
+///
+/// let bounds = AABB::from_two_points(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+/// let grid = VoxelGrid::new((2, 2, 2), bounds, vec![1.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0, 0.0]);
+/// let cloud = HeterogeneousMedium::new(bounds, grid.clone(), grid.max(), Isotropic::new(RGB(0.9, 0.9, 0.9))).scale(3.0);
+///
+/// let transmittance = cloud.transmittance(&Ray::new(Vec3::new(0.5, 0.5, 3.0), Vec3::new(0.0, 0.0, -1.0)), 0.0, f64::MAX);
+/// assert_eq!(transmittance, 1.0);
+/// ```
+pub struct HeterogeneousMedium<'a> {
+    bounds: AABB,
+    density: Arc<dyn Texture + 'a + Send + Sync>,
+    majorant: f64,
+    scale: f64,
+    phase: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> HeterogeneousMedium<'a> {
+    /// Creates new `HeterogeneousMedium` filling `bounds`.
+    pub fn new(bounds: AABB, density: impl Texture + 'a + Send + Sync, majorant: f64, phase: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            bounds,
+            density: Arc::new(density),
+            majorant,
+            scale: 1.0,
+            phase: Arc::new(phase)
+        }
+    }
+
+    /// Sets multiplier of densities and majorant. Default is 1.0.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    /// Estimates fraction of light passing along the ray between `t_min` and `t_max` with ratio tracking.
+    pub fn transmittance(&self, ray: &Ray, t_min: f64, t_max: f64) -> f64 {
+        let majorant = self.majorant * self.scale;
+        let Some((mut t, exit)) = self.bounds.clip(ray, t_min, t_max) else {
+            return 1.0;
+        };
+        if majorant <= 0.0 {
+            return 1.0;
+        }
+
+        let step = 1.0 / (majorant * ray.direction().len());
+        let mut transmittance = 1.0;
+        loop {
+            t -= random_in_range(f64::EPSILON..1.0).ln() * step;
+            if t >= exit {
+                return transmittance;
+            }
+
+            transmittance *= 1.0 - self.density_at(ray, t) / majorant;
+        }
+    }
+
+    fn density_at(&self, ray: &Ray, t: f64) -> f64 {
+        let point = ray.trace(t);
+        (self.density.value(0.0, 0.0, point).luminance() * self.scale).clamp(0.0, self.majorant * self.scale)
+    }
+}
+
+impl<'a> Hit for HeterogeneousMedium<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let majorant = self.majorant * self.scale;
+        let (mut t, exit) = self.bounds.clip(ray, t_min.max(0.0), t_max)?;
+        if majorant <= 0.0 {
+            return None;
+        }
+
+        // Delta tracking: collisions with the majorant are real with probability density / majorant.
+        let step = 1.0 / (majorant * ray.direction().len());
+        loop {
+            t -= random_in_range(f64::EPSILON..1.0).ln() * step;
+            if t >= exit {
+                return None;
+            }
+
+            if self.density_at(ray, t) > majorant * random_in_range(0.0..1.0) {
+                break;
+            }
+        }
+
+        let mut hit_record = HitRecord::new(t, ray.trace(t));
+        hit_record.set_face_normal(ray, -ray.direction().normalize());
+        hit_record.set_material(&*self.phase);
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.bounds
+    }
+}
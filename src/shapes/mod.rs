@@ -1,4 +1,21 @@
 mod sphere;
 mod triangle;
+mod constant_medium;
+mod heterogeneous_medium;
+mod alpha_mask;
+mod transformed;
+mod quad;
+mod disk;
+mod cuboid;
+mod revolution;
+mod cylinder;
+mod cone;
+mod paraboloid;
+mod torus;
+mod plane;
+mod csg;
+mod sdf;
+mod heightfield;
+mod watertight;
 
-pub use {sphere::Sphere, triangle::Triangle};
+pub use {sphere::Sphere, triangle::Triangle, constant_medium::ConstantMedium, heterogeneous_medium::HeterogeneousMedium, alpha_mask::AlphaMask, transformed::Transformed, quad::Quad, disk::Disk, cuboid::Cuboid, cylinder::Cylinder, cone::Cone, paraboloid::Paraboloid, torus::Torus, plane::Plane, csg::Csg, sdf::Sdf, heightfield::Heightfield, watertight::Watertight};
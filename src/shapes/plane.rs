@@ -0,0 +1,77 @@
+use crate::{hit::{Hit, HitRecord}, math::{Ray, Vec3, ONB}, scatter::Scatter, AABB};
+
+use std::sync::Arc;
+
+/// Infinite plane through `point`, facing along `normal`, e.g. ground or wall.\
+/// Texture coordinates repeat in square tiles along two axes of the plane, of size 1 unless changed by `tile`.
+/// Plane is unbounded, so `BVHNode` tests it separately from the hierarchy.
+/// ```
+/// # use rayimg::{shapes::Plane, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+/// let ground = Plane::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB::default()));
+/// let hit_record = ground.hit(&Ray::new(Vec3::new(100.0, 1.5, -300.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert_eq!(hit_record.point(), Vec3::new(100.0, -0.5, -300.0));
+/// assert_eq!(hit_record.normal(), Vec3::new(0.0, 1.0, 0.0));
+/// assert!(!ground.bounding().is_bounded());
+/// ```
+#[derive(Clone)]
+pub struct Plane<'a> {
+    point: Vec3<f64>,
+    onb: ONB,
+    tile: f64,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Plane<'a> {
+    /// Creates new `Plane`.
+    pub fn new(point: Vec3<f64>, normal: Vec3<f64>, material: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            point,
+            onb: ONB::new(normal),
+            tile: 1.0,
+            material: Arc::new(material)
+        }
+    }
+
+    /// Sets size of square tile covered by texture coordinates from `(0, 0)` to `(1, 1)`.
+    /// ```
+    /// # use rayimg::{shapes::Plane, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+    /// let ground = Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0), Lambertian::new(RGB::default())).tile(4.0);
+    /// let (u, v) = ground.hit(&Ray::new(Vec3::new(-3.0, 9.0, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap().uv();
+    /// assert!((0.0..1.0).contains(&u) && (0.0..1.0).contains(&v));
+    /// ```
+    pub fn tile(mut self, size: f64) -> Self {
+        assert!(size > 0.0, "Tile size must be positive");
+        self.tile = size;
+        self
+    }
+}
+
+impl<'a> Hit for Plane<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let normal = self.onb.w();
+        let denominator = normal.dot(&ray.direction());
+        if denominator.abs() < 1e-12 {
+            return None;
+        }
+
+        let t = normal.dot(&(self.point - ray.origin())) / denominator;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        let point = ray.trace(t);
+        let local = self.onb.project(point - self.point);
+
+        let mut hit_record = HitRecord::new(t, point);
+        hit_record.set_face_normal(ray, normal);
+        hit_record.set_uv((local.x / self.tile).rem_euclid(1.0), (local.y / self.tile).rem_euclid(1.0));
+        hit_record.set_tangents(self.onb.u() * self.tile, self.onb.v() * self.tile);
+        hit_record.set_material(&*self.material);
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        AABB::universe()
+    }
+}
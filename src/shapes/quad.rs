@@ -0,0 +1,107 @@
+use crate::{math::{Ray, Vec3}, random::random_in_range, Hit, HitRecord, Scatter, AABB};
+
+use std::sync::Arc;
+
+/// Planar parallelogram spanned by edges `u` and `v` from `corner`, e.g. wall or area light.\
+/// Texture coordinates run from `(0, 0)` at `corner` to `(1, 1)` at `corner + u + v`,
+/// outward normal is `u × v`.
+#[derive(Clone)]
+pub struct Quad<'a> {
+    corner: Vec3<f64>,
+    u: Vec3<f64>,
+    v: Vec3<f64>,
+    w: Vec3<f64>,
+    normal: Vec3<f64>,
+    area: f64,
+    aabb: AABB,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Quad<'a> {
+    /// Creates new `Quad`.
+    /// ```
+    /// # use rayimg::{shapes::Quad, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+    /// let quad = Quad::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 4.0, 0.0), Lambertian::new(RGB::default()));
+    /// let hit_record = quad.hit(&Ray::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    /// assert_eq!(hit_record.uv(), (0.5, 0.25));
+    /// assert_eq!(hit_record.normal(), Vec3::new(0.0, 0.0, 1.0));
+    /// assert_eq!(quad.area(), 8.0);
+    /// ```
+    pub fn new(corner: Vec3<f64>, u: Vec3<f64>, v: Vec3<f64>, material: impl Scatter + 'a + Send + Sync) -> Self {
+        Self::with_material(corner, u, v, Arc::new(material))
+    }
+
+    /// Creates new `Quad` with shared material.
+    pub(crate) fn with_material(corner: Vec3<f64>, u: Vec3<f64>, v: Vec3<f64>, material: Arc<dyn Scatter + 'a + Send + Sync>) -> Self {
+        let n = u.cross(&v);
+        let aabb = AABB::unite(AABB::from_two_points(corner, corner + u + v), AABB::from_two_points(corner + u, corner + v));
+
+        Self {
+            corner,
+            u,
+            v,
+            w: n / n.squared_magnitude(),
+            normal: n.normalize(),
+            area: n.len(),
+            aabb: aabb.pad(1e-4),
+            material
+        }
+    }
+
+    /// Returns area of `Quad`.
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+}
+
+impl<'a> Hit for Quad<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let denominator = self.normal.dot(&ray.direction());
+        if denominator.abs() < 1e-12 {
+            return None;
+        }
+
+        let t = self.normal.dot(&(self.corner - ray.origin())) / denominator;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        let point = ray.trace(t);
+        let planar = point - self.corner;
+        let alpha = self.w.dot(&planar.cross(&self.v));
+        let beta = self.w.dot(&self.u.cross(&planar));
+        if !(0.0..=1.0).contains(&alpha) || !(0.0..=1.0).contains(&beta) {
+            return None;
+        }
+
+        let mut hit_record = HitRecord::new(t, point);
+        hit_record.set_face_normal(ray, self.normal);
+        hit_record.set_uv(alpha, beta);
+        hit_record.set_tangents(self.u, self.v);
+        hit_record.set_material(&*self.material);
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+
+    fn power(&self) -> f64 {
+        self.material.emission().luminance() * self.area * std::f64::consts::PI
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if let Some(hit_record) = self.hit(&Ray::new(origin, direction), 0.001, f64::MAX) {
+            let distance_squared = hit_record.t() * hit_record.t() * direction.squared_magnitude();
+            let cosine = direction.dot(&self.normal).abs() / direction.len();
+            distance_squared / (cosine * self.area)
+        } else {
+            0.0
+        }
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        self.corner + self.u * random_in_range(0.0..1.0) + self.v * random_in_range(0.0..1.0) - origin
+    }
+}
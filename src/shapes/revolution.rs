@@ -0,0 +1,102 @@
+use crate::{hit::HitRecord, math::{Ray, Vec3}, Scatter};
+
+use std::f64::consts::PI;
+
+/// Intersection with surface of revolution around `y` axis, in space of the shape.
+pub(crate) struct LocalHit {
+    pub t: f64,
+    pub point: Vec3<f64>,
+    pub outward: Vec3<f64>,
+    pub uv: (f64, f64),
+    pub tangents: (Vec3<f64>, Vec3<f64>)
+}
+
+impl LocalHit {
+    /// Moves hit from space of the shape with origin at `base` into `HitRecord`.
+    pub fn record<'a>(self, ray: &Ray, base: Vec3<f64>, material: &'a (dyn Scatter + Send + Sync)) -> HitRecord<'a> {
+        let mut hit_record = HitRecord::new(self.t, self.point + base);
+        hit_record.set_face_normal(ray, self.outward.normalize());
+        hit_record.set_uv(self.uv.0, self.uv.1);
+        hit_record.set_tangents(self.tangents.0, self.tangents.1);
+        hit_record.set_material(material);
+        hit_record
+    }
+}
+
+/// Returns angle around `y` axis in `[0, 2π)`, increasing from `+x` towards `-z`.
+pub(crate) fn azimuth(point: Vec3<f64>) -> f64 {
+    (-point.z).atan2(point.x).rem_euclid(2.0 * PI)
+}
+
+/// Returns derivative of point by azimuth.
+pub(crate) fn azimuth_tangent(point: Vec3<f64>) -> Vec3<f64> {
+    Vec3::new(point.z, 0.0, -point.x)
+}
+
+/// Returns real roots of `a t² + b t + c` in ascending order, also when equation is linear.
+pub(crate) fn solve_quadratic(a: f64, b: f64, c: f64) -> Option<(f64, f64)> {
+    if a.abs() < 1e-12 {
+        if b.abs() < 1e-12 {
+            return None;
+        }
+
+        let t = -c / b;
+        return Some((t, t));
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+
+    // Avoids cancellation between `b` and square root of discriminant.
+    let q = -0.5 * (b + b.signum() * discriminant.sqrt());
+    let (t0, t1) = if q == 0.0 { (0.0, 0.0) } else { (q / a, c / q) };
+    Some((t0.min(t1), t0.max(t1)))
+}
+
+/// Flat cap of `radius` at height `y` closing surface of revolution, facing up or down.
+pub(crate) struct Cap {
+    pub y: f64,
+    pub radius: f64,
+    pub phi_max: f64,
+    pub up: bool
+}
+
+impl Cap {
+    /// Intersects ray in space of the shape with the cap.
+    pub fn hit(&self, origin: Vec3<f64>, direction: Vec3<f64>, t_min: f64, t_max: f64) -> Option<LocalHit> {
+        if direction.y.abs() < 1e-12 {
+            return None;
+        }
+
+        let t = (self.y - origin.y) / direction.y;
+        if t < t_min || t_max < t {
+            return None;
+        }
+
+        let point = origin + direction * t;
+        let r = (point.x * point.x + point.z * point.z).sqrt();
+        let phi = azimuth(point);
+        if r > self.radius || phi > self.phi_max {
+            return None;
+        }
+
+        let radial = if r > 1e-9 { Vec3::new(point.x, 0.0, point.z) / r } else { Vec3::new(1.0, 0.0, 0.0) };
+        Some(LocalHit {
+            t,
+            point,
+            outward: Vec3::new(0.0, if self.up { 1.0 } else { -1.0 }, 0.0),
+            uv: (phi / self.phi_max, r / self.radius),
+            tangents: (azimuth_tangent(point) * self.phi_max, radial * self.radius)
+        })
+    }
+}
+
+/// Returns the closer of two optional hits.
+pub(crate) fn closer(first: Option<LocalHit>, second: Option<LocalHit>) -> Option<LocalHit> {
+    match (first, second) {
+        (Some(first), Some(second)) => Some(if second.t < first.t { second } else { first }),
+        (first, second) => first.or(second)
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Vec3, ONB}, scatter::Scatter, Distance, AABB};
+
+use std::sync::Arc;
+
+/// Implicit surface of signed `Distance` function, found by sphere tracing inside `bounds`.\
+/// Normals are estimated from gradient of the distance.
+/// ```
+/// use rayimg::{shapes::Sdf, distances::{Cuboid, Sphere}, materials::Lambertian, math::{Ray, Vec3}, Distance, Hit, RGB, AABB};
+///
+/// let rounded = Cuboid::new(Vec3::new(0.5, 0.5, 0.5)).round(0.1).smooth_union(Sphere::new(0.3).translate(Vec3::new(0.0, 0.7, 0.0)), 0.2);
+/// let bounds = AABB::from_two_points(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.5, 1.0));
+/// let sdf = Sdf::new(rounded, bounds, Lambertian::new(RGB::default()));
+///
+/// let hit_record = sdf.hit(&Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+/// assert!((hit_record.point().z - 0.6).abs() < 1e-4);
+/// assert!((hit_record.normal() - Vec3::new(0.0, 0.0, 1.0)).len() < 1e-4);
+///
+/// // Closures are distance functions too.
+/// let sphere = Sdf::new(|p: Vec3<f64>| p.len() - 1.0, bounds, Lambertian::new(RGB::default()));
+/// assert!(sphere.hit(&Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).is_some());
+/// ```
+pub struct Sdf<'a, D> {
+    distance: D,
+    aabb: AABB,
+    epsilon: f64,
+    max_steps: usize,
+    step: f64,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a, D: Distance> Sdf<'a, D> {
+    /// Creates new `Sdf`. Surface must lie inside `bounds`, marching stops outside of them.
+    pub fn new(distance: D, bounds: AABB, material: impl Scatter + 'a + Send + Sync) -> Self {
+        Self {
+            distance,
+            aabb: bounds,
+            epsilon: 1e-4,
+            max_steps: 256,
+            step: 1.0,
+            material: Arc::new(material)
+        }
+    }
+
+    /// Sets distance from surface at which ray is considered hitting it. Default is 1e-4.
+    pub fn epsilon(mut self, epsilon: f64) -> Self {
+        self.epsilon = epsilon;
+        self
+    }
+
+    /// Sets maximum count of marching steps, ray that doesn't reach the surface in them misses it. Default is 256.
+    pub fn max_steps(mut self, max_steps: usize) -> Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Sets part of distance marched in each step. Default is 1.0, lower values are needed by
+    /// functions that overestimate distance (e.g. `Twist`), at the cost of more steps.
+    pub fn step(mut self, step: f64) -> Self {
+        self.step = step.clamp(1e-3, 1.0);
+        self
+    }
+
+    /// Returns outward normal as gradient of distance, estimated by central differences.
+    fn normal(&self, point: Vec3<f64>) -> Vec3<f64> {
+        let h = self.epsilon;
+        let difference = |offset: Vec3<f64>| self.distance.distance(point + offset) - self.distance.distance(point - offset);
+        Vec3::new(difference(Vec3::new(h, 0.0, 0.0)), difference(Vec3::new(0.0, h, 0.0)), difference(Vec3::new(0.0, 0.0, h))).normalize()
+    }
+}
+
+impl<'a, D: Distance> Hit for Sdf<'a, D> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let (mut t, t_end) = self.aabb.clip(ray, t_min, t_max)?;
+        let speed = ray.direction().len();
+
+        // Ray leaving the surface (e.g. scattered from it) steps away first, so that it doesn't hit it again.
+        let mut distance = self.distance.distance(ray.trace(t));
+        if distance.abs() < self.epsilon {
+            t += 2.0 * self.epsilon / speed;
+            distance = self.distance.distance(ray.trace(t));
+        }
+
+        // Marches on the side of the surface where the ray starts, so rays inside find their way out.
+        let side = if distance < 0.0 { -1.0 } else { 1.0 };
+        for _ in 0..self.max_steps {
+            if t > t_end {
+                return None;
+            }
+
+            if side * distance < self.epsilon {
+                let point = ray.trace(t);
+                let normal = self.normal(point);
+                let onb = ONB::new(normal);
+
+                let mut hit_record = HitRecord::new(t, point);
+                hit_record.set_face_normal(ray, normal);
+                hit_record.set_tangents(onb.u(), onb.v());
+                hit_record.set_material(&*self.material);
+                return Some(hit_record);
+            }
+
+            t += side * distance * self.step / speed;
+            distance = self.distance.distance(ray.trace(t));
+        }
+
+        None
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+}
+
+impl<'a, D: Distance> Solid for Sdf<'a, D> {}
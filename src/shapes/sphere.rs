@@ -1,6 +1,6 @@
-use crate::{hit::{Hit, HitRecord}, math::{Ray, Vec3}, scatter::Scatter, AABB};
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Vec3, ONB}, scatter::Scatter, AABB};
 
-use std::sync::Arc;
+use std::{f64::consts::PI, sync::Arc};
 
 /// Geometric shape, set of points that are all at the same distance called `radius` from the `center`.
 /// Sphere is `Hit`table.
@@ -57,7 +57,7 @@ impl<'a> Sphere<'a> {
 }
 
 impl<'a> Hit for Sphere<'a> {
-    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
         let center_to_origin = ray.origin() - self.center;
         let ray_direction = ray.direction();
         
@@ -83,10 +83,23 @@ impl<'a> Hit for Sphere<'a> {
 
         let normal = (point - self.center) / self.radius;
         hit_record.set_face_normal(ray, normal);
-        
-        if let Some(scatter) = self.material.scatter(ray, &hit_record) {
-            hit_record.set_scatter(scatter);
+
+        let outward = (point - self.center) / self.radius.abs();
+        hit_record.set_uv(((-outward.z).atan2(outward.x) + PI) / (2.0 * PI), (-outward.y).clamp(-1.0, 1.0).acos() / PI);
+
+        let sin_theta = (outward.x * outward.x + outward.z * outward.z).sqrt();
+        if sin_theta > 1e-9 {
+            let radius = self.radius.abs();
+            hit_record.set_tangents(
+                Vec3::new(outward.z, 0.0, -outward.x) * (2.0 * PI * radius),
+                Vec3::new(-outward.x * outward.y / sin_theta, sin_theta, -outward.y * outward.z / sin_theta) * (PI * radius)
+            );
+        } else {
+            // Longitude is undefined at the poles.
+            let onb = ONB::new(outward);
+            hit_record.set_tangents(onb.u(), onb.v());
         }
+        hit_record.set_material(&*self.material);
 
         Some(hit_record)
     }
@@ -94,4 +107,35 @@ impl<'a> Hit for Sphere<'a> {
     fn bounding(&self) -> AABB {
         self.aabb
     }
+
+    fn power(&self) -> f64 {
+        self.material.emission().luminance() * 4.0 * PI * self.radius_squared * PI
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if self.hit(&Ray::new(origin, direction), 0.001, f64::MAX).is_none() {
+            return 0.0;
+        }
+
+        let distance_squared = (self.center - origin).squared_magnitude();
+        if distance_squared <= self.radius_squared {
+            return 0.25 / PI;
+        }
+
+        let cos_theta_max = (1.0 - self.radius_squared / distance_squared).sqrt();
+        1.0 / (2.0 * PI * (1.0 - cos_theta_max))
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        let direction = self.center - origin;
+        let distance_squared = direction.squared_magnitude();
+        if distance_squared <= self.radius_squared {
+            return Vec3::random_unit_vector();
+        }
+
+        let cos_theta_max = (1.0 - self.radius_squared / distance_squared).sqrt();
+        ONB::new(direction).local(Vec3::random_in_cone(cos_theta_max))
+    }
 }
+
+impl<'a> Solid for Sphere<'a> {}
@@ -0,0 +1,161 @@
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Vec3}, scatter::Scatter, AABB};
+use super::revolution::{azimuth, azimuth_tangent, LocalHit};
+
+use std::{f64::consts::PI, sync::Arc};
+
+/// Ring around `y` axis through `center`, with tube of `minor_radius` at `major_radius` from the axis.\
+/// Texture coordinates are angle around the axis and angle around the tube, both divided by `2π`.
+/// ```
+/// # use rayimg::{shapes::Torus, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+/// let torus = Torus::new(Vec3::new(0.0, 0.0, 0.0), 2.0, 0.5, Lambertian::new(RGB::default()));
+/// let hit_record = torus.hit(&Ray::new(Vec3::new(5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert!((hit_record.point() - Vec3::new(2.5, 0.0, 0.0)).len() < 1e-9);
+/// assert!((hit_record.normal() - Vec3::new(1.0, 0.0, 0.0)).len() < 1e-9);
+/// // Ray through the hole misses.
+/// assert!(torus.hit(&Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).is_none());
+/// ```
+#[derive(Clone)]
+pub struct Torus<'a> {
+    center: Vec3<f64>,
+    major_radius: f64,
+    minor_radius: f64,
+    aabb: AABB,
+    material: Arc<dyn Scatter + 'a + Send + Sync>
+}
+
+impl<'a> Torus<'a> {
+    /// Creates new `Torus`.
+    pub fn new(center: Vec3<f64>, major_radius: f64, minor_radius: f64, material: impl Scatter + 'a + Send + Sync) -> Self {
+        let extent = Vec3::new(major_radius + minor_radius, minor_radius, major_radius + minor_radius);
+
+        Self {
+            center,
+            major_radius,
+            minor_radius,
+            aabb: AABB::from_two_points(center - extent, center + extent),
+            material: Arc::new(material)
+        }
+    }
+
+    fn surface(&self, t: f64, point: Vec3<f64>) -> LocalHit {
+        let rho = (point.x * point.x + point.z * point.z).sqrt();
+        let radial = if rho > 1e-12 { Vec3::new(point.x, 0.0, point.z) / rho } else { Vec3::new(1.0, 0.0, 0.0) };
+        let outward = point - radial * self.major_radius;
+
+        let theta = point.y.atan2(rho - self.major_radius).rem_euclid(2.0 * PI);
+        let (sin_theta, cos_theta) = theta.sin_cos();
+
+        LocalHit {
+            t,
+            point,
+            outward,
+            uv: (azimuth(point) / (2.0 * PI), theta / (2.0 * PI)),
+            tangents: (
+                azimuth_tangent(point) * (2.0 * PI),
+                (Vec3::new(0.0, cos_theta, 0.0) - radial * sin_theta) * (2.0 * PI * self.minor_radius)
+            )
+        }
+    }
+}
+
+impl<'a> Hit for Torus<'a> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        // Starts from the bounding box, so that coefficients of the quartic stay small.
+        let (t_enter, t_exit) = self.aabb.clip(ray, t_min, t_max)?;
+        let scale = ray.direction().len();
+        let direction = ray.direction() / scale;
+        let origin = ray.trace(t_enter) - self.center;
+
+        // (|p|² + R² - r²)² = 4R² (x² + z²) along p = origin + s direction.
+        let major_squared = self.major_radius * self.major_radius;
+        let m = origin.dot(&direction);
+        let q = origin.squared_magnitude() + major_squared - self.minor_radius * self.minor_radius;
+        let horizontal = direction.x * direction.x + direction.z * direction.z;
+
+        let roots = solve_quartic(
+            4.0 * m,
+            4.0 * m * m + 2.0 * q - 4.0 * major_squared * horizontal,
+            4.0 * m * q - 8.0 * major_squared * (origin.x * direction.x + origin.z * direction.z),
+            q * q - 4.0 * major_squared * (origin.x * origin.x + origin.z * origin.z)
+        );
+
+        roots.into_iter()
+            .map(|s| (s, t_enter + s / scale))
+            .filter(|(s, t)| *s >= 0.0 && t_min <= *t && *t <= t_max.min(t_exit))
+            .min_by(|a, b| a.1.total_cmp(&b.1))
+            .map(|(s, t)| self.surface(t, origin + direction * s).record(ray, self.center, &*self.material))
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+}
+
+/// Returns real roots of monic `x⁴ + a x³ + b x² + c x + d` by Ferrari's method, polished by Newton's iterations.
+fn solve_quartic(a: f64, b: f64, c: f64, d: f64) -> Vec<f64> {
+    // Depressed quartic y⁴ + p y² + q y + r with x = y - a / 4.
+    let shift = a / 4.0;
+    let p = b - 6.0 * shift * shift;
+    let q = c - 2.0 * b * shift + 8.0 * shift * shift * shift;
+    let r = d - c * shift + b * shift * shift - 3.0 * shift * shift * shift * shift;
+
+    let mut roots = Vec::with_capacity(4);
+    if q.abs() < 1e-12 {
+        // Biquadratic, quadratic in y².
+        let discriminant = p * p - 4.0 * r;
+        if discriminant >= 0.0 {
+            for z in [(-p + discriminant.sqrt()) / 2.0, (-p - discriminant.sqrt()) / 2.0] {
+                if z >= 0.0 {
+                    roots.extend([z.sqrt(), -z.sqrt()]);
+                }
+            }
+        }
+    } else {
+        // Resolvent cubic 8m³ + 8p m² + (2p² - 8r) m - q² = 0 has positive root.
+        let m = largest_cubic_root(p, p * p / 4.0 - r, -q * q / 8.0);
+        if m > 0.0 {
+            let root = (2.0 * m).sqrt();
+            for sign in [1.0, -1.0] {
+                let discriminant = -(2.0 * p + 2.0 * m + sign * 2.0 * q / root);
+                if discriminant >= 0.0 {
+                    roots.extend([(sign * root + discriminant.sqrt()) / 2.0, (sign * root - discriminant.sqrt()) / 2.0]);
+                }
+            }
+        }
+    }
+
+    roots.into_iter().map(|y| {
+        let mut x = y - shift;
+        for _ in 0..2 {
+            let value = (((x + a) * x + b) * x + c) * x + d;
+            let derivative = ((4.0 * x + 3.0 * a) * x + 2.0 * b) * x + c;
+            if derivative.abs() > 1e-12 {
+                x -= value / derivative;
+            }
+        }
+        x
+    }).collect()
+}
+
+/// Returns largest real root of monic `x³ + a x² + b x + c`.
+fn largest_cubic_root(a: f64, b: f64, c: f64) -> f64 {
+    // Depressed cubic t³ + p t + q with x = t - a / 3.
+    let shift = a / 3.0;
+    let p = b - a * shift;
+    let q = 2.0 * shift * shift * shift - b * shift + c;
+
+    let discriminant = q * q / 4.0 + p * p * p / 27.0;
+    let t = if discriminant > 0.0 {
+        let root = discriminant.sqrt();
+        (-q / 2.0 + root).cbrt() + (-q / 2.0 - root).cbrt()
+    } else if p < 0.0 {
+        let amplitude = 2.0 * (-p / 3.0).sqrt();
+        amplitude * ((3.0 * q / (p * amplitude)).clamp(-1.0, 1.0).acos() / 3.0).cos()
+    } else {
+        0.0
+    };
+
+    t - shift
+}
+
+impl<'a> Solid for Torus<'a> {}
@@ -0,0 +1,84 @@
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Transform, Vec3}, AABB};
+
+/// Shape placed in the scene by `Transform`, e.g. rotated box or scaled sphere.\
+/// Rays are transformed into space of the shape and normals back, so any `Hit` object can be transformed.
+/// Wrapping shared `Arc` creates instances of one object (e.g. big mesh in `BVHNode`) at different places.
+/// ```
+/// use rayimg::{shapes::{Sphere, Transformed}, materials::Lambertian, math::{Ray, Transform, Vec3}, Hit, RGB};
+/// use std::sync::Arc;
+///
+/// let sphere = Arc::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+/// let ellipsoid = Transformed::new(sphere.clone(), Transform::new().scale(Vec3::new(2.0, 1.0, 1.0)).translate(Vec3::new(0.0, 0.0, -5.0)));
+///
+/// let hit_record = ellipsoid.hit(&Ray::new(Vec3::new(-10.0, 0.0, -5.0), Vec3::new(1.0, 0.0, 0.0)), 0.001, f64::MAX).unwrap();
+/// assert_eq!(hit_record.point(), Vec3::new(-2.0, 0.0, -5.0));
+/// assert_eq!(hit_record.normal(), Vec3::new(-1.0, 0.0, 0.0));
+/// ```
+pub struct Transformed<H> {
+    shape: H,
+    transform: Transform,
+    aabb: AABB
+}
+
+impl<H: Hit> Transformed<H> {
+    /// Creates new `Transformed` shape.
+    pub fn new(shape: H, transform: Transform) -> Self {
+        Self {
+            aabb: transform.aabb(&shape.bounding()),
+            shape,
+            transform
+        }
+    }
+
+    /// Returns transformation of the shape.
+    pub fn transform(&self) -> Transform {
+        self.transform
+    }
+
+    /// Returns ratio of solid angles of direction seen from transformed space and from space of the shape.
+    fn solid_angle_ratio(&self, direction: Vec3<f64>) -> f64 {
+        let inverse = self.transform.inverse();
+        inverse.matrix().determinant().abs() / inverse.vector(direction.normalize()).len().powi(3)
+    }
+}
+
+impl<H: Hit> Hit for Transformed<H> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        let local = self.shape.hit(&self.transform.inverse().ray(ray), t_min, t_max)?;
+        let outward = |normal: Vec3<f64>| self.transform.normal(if local.front_face() { normal } else { -normal }).normalize();
+
+        let mut hit_record = HitRecord::new(local.t(), self.transform.point(local.point()));
+        hit_record.set_face_normal(ray, outward(local.geometric_normal()));
+        hit_record.set_shading_normal(outward(local.normal()));
+
+        let (u, v) = local.uv();
+        let (dpdu, dpdv) = local.tangents();
+        hit_record.set_uv(u, v);
+        hit_record.set_tangents(self.transform.vector(dpdu), self.transform.vector(dpdv));
+        if let Some(material) = local.material() {
+            hit_record.set_material(material);
+        }
+
+        Some(hit_record)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.aabb
+    }
+
+    fn power(&self) -> f64 {
+        // Area grows with square of average scale.
+        self.shape.power() * self.transform.matrix().determinant().abs().powf(2.0 / 3.0)
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        let inverse = self.transform.inverse();
+        self.shape.pdf_value(inverse.point(origin), inverse.vector(direction)) * self.solid_angle_ratio(direction)
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        self.transform.vector(self.shape.random_direction(self.transform.inverse().point(origin)))
+    }
+}
+
+impl<H: Solid> Solid for Transformed<H> {}
@@ -1,27 +1,98 @@
-use crate::{math::{Ray, Vec3}, Hit, HitRecord, Scatter, AABB};
+use crate::{math::{Ray, Vec3, ONB}, random::random_in_range, Hit, HitRecord, Scatter, AABB};
 
 use std::sync::Arc;
 
 /// 2D Triangle with determined as plane between three points.
+#[derive(Clone)]
 pub struct Triangle<'a> {
     vertices: [Vec3<f64>; 3],
     edges: [Vec3<f64>; 2],
+    normal: Vec3<f64>,
+    normals: Option<[Vec3<f64>; 3]>,
+    uvs: [(f64, f64); 3],
+    tangents: (Vec3<f64>, Vec3<f64>),
+    area: f64,
+    aabb: AABB,
     material: Arc<dyn Scatter + 'a + Send + Sync>
 }
 
 impl<'a> Triangle<'a> {
     /// Creates new `Triangle`.
     pub fn new(vertices: [Vec3<f64>; 3], material: impl Scatter + 'a + Send + Sync) -> Self {
-        Self {
+        let edges = [vertices[1] - vertices[0], vertices[2] - vertices[0]];
+        let cross = edges[0].cross(&edges[1]);
+
+        let aabb = AABB::unite(AABB::from_two_points(vertices[0], vertices[1]), AABB::from_two_points(vertices[0], vertices[2]));
+
+        let mut triangle = Self {
             vertices,
-            edges: [vertices[1] - vertices[0], vertices[2] - vertices[0]],
+            edges,
+            normal: cross.normalize(),
+            normals: None,
+            uvs: [(0.0, 0.0), (1.0, 0.0), (0.0, 1.0)],
+            tangents: (Vec3::default(), Vec3::default()),
+            area: cross.len() * 0.5,
+            aabb: aabb.pad(1e-4),
             material: Arc::new(material)
+        };
+
+        triangle.tangents = triangle.compute_tangents();
+        triangle
+    }
+
+    /// Sets outward vertex normals, interpolated across the surface into shading normal.
+    pub fn normals(mut self, normals: [Vec3<f64>; 3]) -> Self {
+        self.normals = Some(normals.map(|normal| normal.normalize()));
+        self
+    }
+
+    /// Sets texture coordinates of vertices. By default barycentric coordinates are used,
+    /// so vertices have coordinates `(0, 0)`, `(1, 0)` and `(0, 1)`.
+    /// ```
+    /// # use rayimg::{shapes::Triangle, math::{Vec3, Ray}, materials::Lambertian, Hit, RGB};
+    /// let triangle = Triangle::new([Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)], Lambertian::new(RGB::default()))
+    ///     .uvs([(0.0, 0.0), (0.5, 0.0), (0.0, 0.5)]);
+    /// let hit_record = triangle.hit(&Ray::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    /// assert_eq!(hit_record.uv(), (0.25, 0.25));
+    /// assert_eq!(hit_record.tangents(), (Vec3::new(4.0, 0.0, 0.0), Vec3::new(0.0, 4.0, 0.0)));
+    /// ```
+    pub fn uvs(mut self, uvs: [(f64, f64); 3]) -> Self {
+        self.uvs = uvs;
+        self.tangents = self.compute_tangents();
+        self
+    }
+
+    /// Returns area of `Triangle`.
+    /// ```
+    /// # use rayimg::{shapes::Triangle, math::Vec3, materials::Lambertian, RGB};
+    /// let triangle = Triangle::new([Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0)], Lambertian::new(RGB::default()));
+    /// assert_eq!(triangle.area(), 2.0);
+    /// ```
+    pub fn area(&self) -> f64 {
+        self.area
+    }
+
+    /// Solves for `dpdu` and `dpdv` from edges and differences of texture coordinates along them.
+    fn compute_tangents(&self) -> (Vec3<f64>, Vec3<f64>) {
+        let (du1, dv1) = (self.uvs[1].0 - self.uvs[0].0, self.uvs[1].1 - self.uvs[0].1);
+        let (du2, dv2) = (self.uvs[2].0 - self.uvs[0].0, self.uvs[2].1 - self.uvs[0].1);
+
+        let determinant = du1 * dv2 - dv1 * du2;
+        if determinant.abs() < 1e-12 {
+            // Degenerate mapping has no derivatives, so any frame around the normal will do.
+            let onb = ONB::new(self.normal);
+            return (onb.u(), onb.v());
         }
+
+        (
+            (self.edges[0] * dv2 - self.edges[1] * dv1) / determinant,
+            (self.edges[1] * du1 - self.edges[0] * du2) / determinant
+        )
     }
 }
 
 impl<'a> Hit for Triangle<'a> {
-    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
         let ray_direction = ray.direction();
 
         let h = ray_direction.cross(&self.edges[1]);
@@ -50,16 +121,46 @@ impl<'a> Hit for Triangle<'a> {
 
         let mut hit_record = HitRecord::new(t, ray.trace(t));
         
-        hit_record.set_face_normal(&ray, self.edges[0].cross(&self.edges[1]).normalize());
-        
-        if let Some(scatter) = self.material.scatter(ray, &hit_record) {
-            hit_record.set_scatter(scatter);
+        hit_record.set_face_normal(ray, self.normal);
+        if let Some(normals) = self.normals {
+            hit_record.set_shading_normal((normals[0] * (1.0 - u - v) + normals[1] * u + normals[2] * v).normalize());
         }
+
+        let w = 1.0 - u - v;
+        hit_record.set_uv(
+            self.uvs[0].0 * w + self.uvs[1].0 * u + self.uvs[2].0 * v,
+            self.uvs[0].1 * w + self.uvs[1].1 * u + self.uvs[2].1 * v
+        );
+        hit_record.set_tangents(self.tangents.0, self.tangents.1);
+        hit_record.set_material(&*self.material);
         
         Some(hit_record)
     }
 
     fn bounding(&self) -> AABB {
-        todo!()
+        self.aabb
+    }
+
+    fn power(&self) -> f64 {
+        self.material.emission().luminance() * self.area * std::f64::consts::PI
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        if let Some(hit_record) = self.hit(&Ray::new(origin, direction), 0.001, f64::MAX) {
+            let distance_squared = hit_record.t() * hit_record.t() * direction.squared_magnitude();
+            let cosine = direction.dot(&self.normal).abs() / direction.len();
+            distance_squared / (cosine * self.area)
+        } else {
+            0.0
+        }
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        let (mut a, mut b): (f64, f64) = (random_in_range(0.0..1.0), random_in_range(0.0..1.0));
+        if a + b > 1.0 {
+            (a, b) = (1.0 - a, 1.0 - b);
+        }
+
+        self.vertices[0] + self.edges[0] * a + self.edges[1] * b - origin
     }
 }
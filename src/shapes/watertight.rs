@@ -0,0 +1,54 @@
+use crate::{hit::{Hit, HitRecord, Solid}, math::{Ray, Vec3}, AABB};
+
+/// Object declared closed by the caller, so `Csg` can combine it, e.g. `BVHNode` of triangles of a watertight model.\
+/// Parts of the object must neither overlap nor leave gaps, otherwise entries and exits of its crossings don't pair up.
+/// ```
+/// use rayimg::{shapes::{Csg, Sphere, Triangle, Watertight}, materials::Lambertian, math::{Ray, Vec3}, Hit, Scene, RGB};
+///
+/// // Tetrahedron with outward winding.
+/// let [a, b, c, d] = [Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, 1.0)];
+/// let mut mesh = Scene::new();
+/// for vertices in [[a, c, b], [a, b, d], [a, d, c], [b, c, d]] {
+///     mesh.add_object(Triangle::new(vertices, Lambertian::new(RGB::default())));
+/// }
+///
+/// let cut = Csg::difference(Watertight::new(mesh), Sphere::new(Vec3::new(0.0, 0.0, 0.0), 0.5, Lambertian::new(RGB::default())));
+/// let hit_record = cut.hit(&Ray::new(Vec3::new(0.1, 0.1, -1.0), Vec3::new(0.0, 0.0, 1.0)), 0.001, f64::MAX).unwrap();
+/// assert!(hit_record.point().z > 0.4);
+/// ```
+pub struct Watertight<H> {
+    object: H
+}
+
+impl<H: Hit> Watertight<H> {
+    /// Creates new `Watertight` object.
+    pub fn new(object: H) -> Self {
+        Self {
+            object
+        }
+    }
+}
+
+impl<H: Hit> Hit for Watertight<H> {
+    fn hit(&self, ray: &Ray, t_min: f64, t_max: f64) -> Option<HitRecord<'_>> {
+        self.object.hit(ray, t_min, t_max)
+    }
+
+    fn bounding(&self) -> AABB {
+        self.object.bounding()
+    }
+
+    fn power(&self) -> f64 {
+        self.object.power()
+    }
+
+    fn pdf_value(&self, origin: Vec3<f64>, direction: Vec3<f64>) -> f64 {
+        self.object.pdf_value(origin, direction)
+    }
+
+    fn random_direction(&self, origin: Vec3<f64>) -> Vec3<f64> {
+        self.object.random_direction(origin)
+    }
+}
+
+impl<H: Hit> Solid for Watertight<H> {}
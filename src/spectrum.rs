@@ -0,0 +1,150 @@
+use crate::{random::random_in_range, rgb::RGB};
+
+use std::sync::OnceLock;
+
+/// Shortest wavelength of visible light in nanometers.
+pub const WAVELENGTH_MIN: f64 = 380.0;
+
+/// Longest wavelength of visible light in nanometers.
+pub const WAVELENGTH_MAX: f64 = 780.0;
+
+/// Returns CIE 1931 color matching functions at `wavelength` in nanometers,
+/// using multi-lobe fit by Wyman, Sloan and Shirley.
+pub fn cie_xyz(wavelength: f64) -> (f64, f64, f64) {
+    let lobe = |mean: f64, lower: f64, upper: f64| {
+        let t = (wavelength - mean) / if wavelength < mean { lower } else { upper };
+        (-0.5 * t * t).exp()
+    };
+
+    let x = 1.056 * lobe(599.8, 37.9, 31.0) + 0.362 * lobe(442.0, 16.0, 26.7) - 0.065 * lobe(501.1, 20.4, 26.2);
+    let y = 0.821 * lobe(568.8, 46.9, 40.5) + 0.286 * lobe(530.9, 16.3, 31.1);
+    let z = 1.217 * lobe(437.0, 11.8, 36.0) + 0.681 * lobe(459.0, 26.0, 13.8);
+    (x, y, z)
+}
+
+/// Returns color of single `wavelength` scaled so that its average over uniformly chosen visible wavelengths is white.
+pub fn wavelength_weight(wavelength: f64) -> RGB {
+    static NORMALIZATION: OnceLock<RGB> = OnceLock::new();
+
+    let rgb = |wavelength: f64| {
+        let (x, y, z) = cie_xyz(wavelength);
+        let color = RGB::from_xyz(x, y, z);
+        RGB(color.0.max(0.0), color.1.max(0.0), color.2.max(0.0))
+    };
+
+    let normalization = NORMALIZATION.get_or_init(|| {
+        const STEPS: usize = 1000;
+        let mut sum = RGB::default();
+        for step in 0..STEPS {
+            sum += rgb(WAVELENGTH_MIN + (step as f64 + 0.5) / STEPS as f64 * (WAVELENGTH_MAX - WAVELENGTH_MIN));
+        }
+        sum / STEPS as f64
+    });
+
+    let color = rgb(wavelength);
+    RGB(color.0 / normalization.0, color.1 / normalization.1, color.2 / normalization.2)
+}
+
+/// Number of wavelengths traced together along each path in spectral mode.
+pub const WAVELENGTH_COUNT: usize = 4;
+
+/// Radiance or reflectance at `WAVELENGTH_COUNT` wavelengths.
+pub type Spectrum = [f64; WAVELENGTH_COUNT];
+
+/// Chooses random hero wavelength and the rest equally spaced after it, wrapping around visible range.
+pub fn sample_wavelengths() -> Spectrum {
+    let range = WAVELENGTH_MAX - WAVELENGTH_MIN;
+    let hero = random_in_range(0.0..range);
+
+    let mut wavelengths = [0.0; WAVELENGTH_COUNT];
+    for (index, wavelength) in wavelengths.iter_mut().enumerate() {
+        *wavelength = WAVELENGTH_MIN + (hero + index as f64 * range / WAVELENGTH_COUNT as f64) % range;
+    }
+
+    wavelengths
+}
+
+/// Smooth nonnegative basis functions of red, green and blue parts of the spectrum summing to one at every wavelength.
+fn basis(wavelength: f64) -> [f64; 3] {
+    let lobe = |mean: f64, width: f64| (-0.5 * ((wavelength - mean) / width).powi(2)).exp();
+    let lobes = [lobe(620.0, 40.0), lobe(540.0, 35.0), lobe(450.0, 35.0)];
+    let sum = lobes.iter().sum::<f64>().max(f64::MIN_POSITIVE);
+    lobes.map(|value| value / sum)
+}
+
+/// Linear RGB colors of equal-energy spectrum and of each basis function, with white mapped to `RGB(1.0, 1.0, 1.0)`.
+struct Projection {
+    white: RGB,
+    inverse_basis: [[f64; 3]; 3]
+}
+
+fn projection() -> &'static Projection {
+    static PROJECTION: OnceLock<Projection> = OnceLock::new();
+
+    PROJECTION.get_or_init(|| {
+        const STEPS: usize = 1000;
+        let step = (WAVELENGTH_MAX - WAVELENGTH_MIN) / STEPS as f64;
+
+        let (mut white, mut colors) = ((0.0, 0.0, 0.0), [(0.0, 0.0, 0.0); 3]);
+        for index in 0..STEPS {
+            let wavelength = WAVELENGTH_MIN + (index as f64 + 0.5) * step;
+            let (x, y, z) = cie_xyz(wavelength);
+            white = (white.0 + x * step, white.1 + y * step, white.2 + z * step);
+            for (color, weight) in colors.iter_mut().zip(basis(wavelength)) {
+                *color = (color.0 + x * weight * step, color.1 + y * weight * step, color.2 + z * weight * step);
+            }
+        }
+
+        let white = RGB::from_xyz(white.0, white.1, white.2);
+        let columns = colors.map(|(x, y, z)| {
+            let color = RGB::from_xyz(x, y, z);
+            [color.0 / white.0, color.1 / white.1, color.2 / white.2]
+        });
+
+        Projection {
+            white,
+            inverse_basis: invert([[columns[0][0], columns[1][0], columns[2][0]], [columns[0][1], columns[1][1], columns[2][1]], [columns[0][2], columns[1][2], columns[2][2]]])
+        }
+    })
+}
+
+fn invert(m: [[f64; 3]; 3]) -> [[f64; 3]; 3] {
+    let cofactor = |r0: usize, r1: usize, c0: usize, c1: usize| m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0];
+    let determinant = m[0][0] * cofactor(1, 2, 1, 2) - m[0][1] * cofactor(1, 2, 0, 2) + m[0][2] * cofactor(1, 2, 0, 1);
+
+    [
+        [cofactor(1, 2, 1, 2) / determinant, -cofactor(0, 2, 1, 2) / determinant, cofactor(0, 1, 1, 2) / determinant],
+        [-cofactor(1, 2, 0, 2) / determinant, cofactor(0, 2, 0, 2) / determinant, -cofactor(0, 1, 0, 2) / determinant],
+        [cofactor(1, 2, 0, 1) / determinant, -cofactor(0, 2, 0, 1) / determinant, cofactor(0, 1, 0, 1) / determinant]
+    ]
+}
+
+/// Returns values at `wavelengths` of smooth spectrum whose color is `color`.
+/// Colors far outside of RGB gamut are approximated, since spectra can't be negative.
+pub fn upsample(color: RGB, wavelengths: &Spectrum) -> Spectrum {
+    let inverse = &projection().inverse_basis;
+    let weights = [0, 1, 2].map(|row| inverse[row][0] * color.0 + inverse[row][1] * color.1 + inverse[row][2] * color.2);
+
+    wavelengths.map(|wavelength| {
+        let basis = basis(wavelength);
+        (weights[0] * basis[0] + weights[1] * basis[1] + weights[2] * basis[2]).max(0.0)
+    })
+}
+
+/// Converts radiance at wavelengths chosen by `sample_wavelengths` into linear RGB estimate,
+/// accumulating CIE XYZ and mapping equal-energy white to `RGB(1.0, 1.0, 1.0)`.
+pub fn to_rgb(radiance: &Spectrum, wavelengths: &Spectrum) -> RGB {
+    let scale = (WAVELENGTH_MAX - WAVELENGTH_MIN) / WAVELENGTH_COUNT as f64;
+
+    let (mut x, mut y, mut z) = (0.0, 0.0, 0.0);
+    for (value, &wavelength) in radiance.iter().zip(wavelengths) {
+        let (cie_x, cie_y, cie_z) = cie_xyz(wavelength);
+        x += value * cie_x * scale;
+        y += value * cie_y * scale;
+        z += value * cie_z * scale;
+    }
+
+    let white = projection().white;
+    let color = RGB::from_xyz(x, y, z);
+    RGB(color.0 / white.0, color.1 / white.1, color.2 / white.2)
+}
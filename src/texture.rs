@@ -0,0 +1,21 @@
+use crate::{math::Vec3, rgb::RGB};
+
+/// Describes color (or some other property, e.g. roughness) of the surface at a point.
+pub trait Texture {
+    /// Returns value at surface coordinates `u`, `v` and hit `point`.
+    fn value(&self, u: f64, v: f64, point: Vec3<f64>) -> RGB;
+}
+
+/// Constant color.
+impl Texture for RGB {
+    fn value(&self, _: f64, _: f64, _: Vec3<f64>) -> RGB {
+        *self
+    }
+}
+
+/// Constant grayscale value.
+impl Texture for f64 {
+    fn value(&self, _: f64, _: f64, _: Vec3<f64>) -> RGB {
+        RGB(*self, *self, *self)
+    }
+}
@@ -0,0 +1,42 @@
+use crate::{math::Vec3, rgb::RGB, texture::Texture};
+
+use std::sync::Arc;
+
+/// Solid 3D checker pattern alternating two textures in cubes of size `scale`.
+/// ```
+/// use rayimg::{textures::Checker, math::Vec3, Texture, RGB};
+///
+/// let checker = Checker::new(1.0, RGB(1.0, 1.0, 1.0), RGB(0.0, 0.0, 0.0));
+/// assert_eq!(checker.value(0.0, 0.0, Vec3::new(0.5, 0.5, 0.5)), RGB(1.0, 1.0, 1.0));
+/// assert_eq!(checker.value(0.0, 0.0, Vec3::new(1.5, 0.5, 0.5)), RGB(0.0, 0.0, 0.0));
+/// ```
+#[derive(Clone)]
+pub struct Checker<'a> {
+    inverse_scale: f64,
+    even: Arc<dyn Texture + 'a + Send + Sync>,
+    odd: Arc<dyn Texture + 'a + Send + Sync>
+}
+
+impl<'a> Checker<'a> {
+    /// Creates new `Checker` texture.
+    pub fn new(scale: f64, even: impl Texture + 'a + Send + Sync, odd: impl Texture + 'a + Send + Sync) -> Self {
+        Self {
+            inverse_scale: 1.0 / scale,
+            even: Arc::new(even),
+            odd: Arc::new(odd)
+        }
+    }
+}
+
+impl<'a> Texture for Checker<'a> {
+    fn value(&self, u: f64, v: f64, point: Vec3<f64>) -> RGB {
+        let scaled = point * self.inverse_scale;
+        let sum = scaled.x.floor() as i64 + scaled.y.floor() as i64 + scaled.z.floor() as i64;
+
+        if sum.rem_euclid(2) == 0 {
+            self.even.value(u, v, point)
+        } else {
+            self.odd.value(u, v, point)
+        }
+    }
+}
@@ -0,0 +1,123 @@
+use crate::{image_read::read_image, math::Vec3, rgb::RGB, texture::Texture};
+
+use std::{io, path::Path};
+
+/// Describes how texture coordinates outside of `0.0..=1.0` are mapped onto the image.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wrap {
+    /// Image is tiled.
+    Repeat,
+    /// Edge pixels are stretched.
+    Clamp,
+    /// Image is tiled with every other tile mirrored.
+    Mirror
+}
+
+/// Texture mapping image onto surface by its `u` and `v` coordinates with bilinear filtering.\
+/// `v = 0.0` is the bottom row of the image.
+/// ```
+/// use rayimg::{textures::{ImageTexture, Wrap}, math::Vec3, Texture, RGB};
+///
+/// let texture = ImageTexture::new(2, 1, vec![RGB(0.0, 0.0, 0.0), RGB(1.0, 1.0, 1.0)]).wrap(Wrap::Clamp);
+/// assert_eq!(texture.value(0.0, 0.5, Vec3::default()), RGB(0.0, 0.0, 0.0));
+/// assert_eq!(texture.value(0.5, 0.5, Vec3::default()), RGB(0.5, 0.5, 0.5));
+/// assert_eq!(texture.value(1.0, 0.5, Vec3::default()), RGB(1.0, 1.0, 1.0));
+/// ```
+#[derive(Debug, Clone)]
+pub struct ImageTexture {
+    width: usize,
+    height: usize,
+    pixels: Vec<RGB>,
+    wrap: Wrap
+}
+
+impl ImageTexture {
+    /// Creates new `ImageTexture` from `width * height` linear colors starting from upper left corner.
+    pub fn new(width: usize, height: usize, pixels: Vec<RGB>) -> Self {
+        assert!(width > 0 && height > 0, "Image must not be empty");
+        assert_eq!(pixels.len(), width * height, "Pixel count does not match image bounds");
+
+        Self {
+            width,
+            height,
+            pixels,
+            wrap: Wrap::Repeat
+        }
+    }
+
+    /// Loads `ImageTexture` from `.ppm`, `.pgm`, `.png` or `.hdr` file. 8-bit and 16-bit images are converted from gamma 2.0 to linear colors.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        let image = read_image(path.as_ref())?;
+        let pixels = if image.gamma_encoded {
+            image.pixels.into_iter().map(|pixel| pixel * pixel).collect()
+        } else {
+            image.pixels
+        };
+
+        Ok(Self::new(image.width, image.height, pixels))
+    }
+
+    /// Loads `ImageTexture` keeping stored values as they are. Used for data like normal or height maps,
+    /// which are not gamma encoded.
+    pub fn load_linear(path: impl AsRef<Path>) -> io::Result<Self> {
+        let image = read_image(path.as_ref())?;
+        Ok(Self::new(image.width, image.height, image.pixels))
+    }
+
+    /// Loads opacity stored in alpha channel of `.png` file as grayscale `ImageTexture`, e.g. for `AlphaMask`.
+    /// Images without alpha channel are fully opaque.
+    pub fn load_alpha(path: impl AsRef<Path>) -> io::Result<Self> {
+        let image = read_image(path.as_ref())?;
+        let pixels = match image.alpha {
+            Some(alpha) => alpha.into_iter().map(|alpha| RGB(alpha, alpha, alpha)).collect(),
+            None => vec![RGB(1.0, 1.0, 1.0); image.width * image.height]
+        };
+
+        Ok(Self::new(image.width, image.height, pixels))
+    }
+
+    /// Sets wrap mode. Default is `Wrap::Repeat`.
+    pub fn wrap(mut self, wrap: Wrap) -> Self {
+        self.wrap = wrap;
+        self
+    }
+
+    /// Returns image width and height.
+    pub fn bounds(&self) -> (usize, usize) {
+        (self.width, self.height)
+    }
+
+    fn texel(&self, x: i64, y: i64) -> RGB {
+        let x = Self::wrap_index(x, self.width, self.wrap);
+        let y = Self::wrap_index(y, self.height, self.wrap);
+        self.pixels[y * self.width + x]
+    }
+
+    fn wrap_index(index: i64, size: usize, wrap: Wrap) -> usize {
+        let size = size as i64;
+        let index = match wrap {
+            Wrap::Repeat => index.rem_euclid(size),
+            Wrap::Clamp => index.clamp(0, size - 1),
+            Wrap::Mirror => {
+                let index = index.rem_euclid(2 * size);
+                if index >= size { 2 * size - 1 - index } else { index }
+            }
+        };
+
+        index as usize
+    }
+}
+
+impl Texture for ImageTexture {
+    fn value(&self, u: f64, v: f64, _: Vec3<f64>) -> RGB {
+        let x = u * self.width as f64 - 0.5;
+        let y = (1.0 - v) * self.height as f64 - 0.5;
+        let (x0, y0) = (x.floor(), y.floor());
+        let (fx, fy) = (x - x0, y - y0);
+        let (x0, y0) = (x0 as i64, y0 as i64);
+
+        let top = self.texel(x0, y0) * (1.0 - fx) + self.texel(x0 + 1, y0) * fx;
+        let bottom = self.texel(x0, y0 + 1) * (1.0 - fx) + self.texel(x0 + 1, y0 + 1) * fx;
+        top * (1.0 - fy) + bottom * fy
+    }
+}
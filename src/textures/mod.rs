@@ -0,0 +1,6 @@
+mod checker;
+mod noise;
+mod image_texture;
+mod voxel_grid;
+
+pub use {checker::Checker, noise::Noise, image_texture::{ImageTexture, Wrap}, voxel_grid::VoxelGrid};
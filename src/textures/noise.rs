@@ -0,0 +1,70 @@
+use crate::{math::{Perlin, Vec3}, rgb::RGB, texture::Texture};
+
+#[derive(Debug, Clone, Copy)]
+enum Pattern {
+    Perlin,
+    Turbulence,
+    Marble
+}
+
+/// Procedural texture based on `Perlin` noise.
+/// ```
+/// use rayimg::{textures::Noise, math::Vec3, Texture, RGB};
+///
+/// let marble = Noise::marble(4.0, 7).color(RGB(0.9, 0.9, 1.0));
+/// let value = marble.value(0.0, 0.0, Vec3::new(0.2, 0.4, 0.1));
+/// assert!(0.0 <= value.b() && value.b() <= 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Noise {
+    perlin: Perlin,
+    pattern: Pattern,
+    scale: f64,
+    depth: usize,
+    color: RGB
+}
+
+impl Noise {
+    /// Creates smooth noise texture, `scale` is frequency of noise.
+    pub fn perlin(scale: f64) -> Self {
+        Self::new(Pattern::Perlin, scale, 1)
+    }
+
+    /// Creates turbulence texture summing `depth` octaves of noise.
+    pub fn turbulence(scale: f64, depth: usize) -> Self {
+        Self::new(Pattern::Turbulence, scale, depth)
+    }
+
+    /// Creates marble-like stripes along `z` axis distorted by turbulence.
+    pub fn marble(scale: f64, depth: usize) -> Self {
+        Self::new(Pattern::Marble, scale, depth)
+    }
+
+    /// Sets color which is multiplied by noise value. Default is white.
+    pub fn color(mut self, color: RGB) -> Self {
+        self.color = color;
+        self
+    }
+
+    fn new(pattern: Pattern, scale: f64, depth: usize) -> Self {
+        Self {
+            perlin: Perlin::new(),
+            pattern,
+            scale,
+            depth,
+            color: RGB(1.0, 1.0, 1.0)
+        }
+    }
+}
+
+impl Texture for Noise {
+    fn value(&self, _: f64, _: f64, point: Vec3<f64>) -> RGB {
+        let value = match self.pattern {
+            Pattern::Perlin => 0.5 * (1.0 + self.perlin.noise(point * self.scale)),
+            Pattern::Turbulence => self.perlin.turbulence(point * self.scale, self.depth),
+            Pattern::Marble => 0.5 * (1.0 + (self.scale * point.z + 10.0 * self.perlin.turbulence(point, self.depth)).sin())
+        };
+
+        self.color * value.clamp(0.0, 1.0)
+    }
+}
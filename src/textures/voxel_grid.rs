@@ -0,0 +1,142 @@
+use crate::{math::Vec3, rgb::RGB, texture::Texture, AABB};
+
+use std::{fs::File, io::{self, BufReader, Read}, path::Path};
+
+/// Dense 3D grid of scalar values (e.g. smoke density) placed in `bounds` and sampled by hit point with trilinear filtering.\
+/// Points outside of the bounds have value 0.0.
+/// ```
+/// use rayimg::{textures::VoxelGrid, math::Vec3, Texture, AABB};
+///
+/// let bounds = AABB::from_two_points(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 1.0, 1.0));
+/// let grid = VoxelGrid::new((2, 1, 1), bounds, vec![0.0, 1.0]);
+/// assert_eq!(grid.value(0.0, 0.0, Vec3::new(1.0, 0.5, 0.5)).r(), 0.5);
+/// assert_eq!(grid.value(0.0, 0.0, Vec3::new(3.0, 0.5, 0.5)).r(), 0.0);
+/// assert_eq!(grid.max(), 1.0);
+/// ```
+#[derive(Debug, Clone)]
+pub struct VoxelGrid {
+    resolution: (usize, usize, usize),
+    bounds: AABB,
+    values: Vec<f64>,
+    max: f64
+}
+
+impl VoxelGrid {
+    /// Creates new `VoxelGrid` from values ordered with `x` changing fastest and `z` slowest.
+    pub fn new(resolution: (usize, usize, usize), bounds: AABB, values: Vec<f64>) -> Self {
+        assert!(resolution.0 > 0 && resolution.1 > 0 && resolution.2 > 0, "Voxel grid must not be empty");
+        assert_eq!(values.len(), resolution.0 * resolution.1 * resolution.2, "Value count does not match voxel grid resolution");
+
+        Self {
+            resolution,
+            bounds,
+            max: values.iter().copied().fold(0.0, f64::max),
+            values
+        }
+    }
+
+    /// Loads `VoxelGrid` from Mitsuba `.vol` file with 32-bit float values. Multiple channels are averaged.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        Self::from_reader(BufReader::new(File::open(path)?))
+    }
+
+    /// Reads `VoxelGrid` in Mitsuba `.vol` format: `VOL` signature, version 3, encoding 1 (float32),
+    /// resolution, channel count and bounding box followed by little-endian values.
+    pub fn from_reader(mut reader: impl Read) -> io::Result<Self> {
+        let invalid_data = |message: &str| io::Error::new(io::ErrorKind::InvalidData, message);
+
+        let mut signature = [0; 4];
+        reader.read_exact(&mut signature)?;
+        if &signature[..3] != b"VOL" || signature[3] != 3 {
+            return Err(invalid_data("missing VOL signature"));
+        }
+
+        let mut read_u32 = || -> io::Result<u32> {
+            let mut bytes = [0; 4];
+            reader.read_exact(&mut bytes)?;
+            Ok(u32::from_le_bytes(bytes))
+        };
+
+        if read_u32()? != 1 {
+            return Err(invalid_data("unsupported voxel encoding"));
+        }
+
+        let resolution = (read_u32()? as usize, read_u32()? as usize, read_u32()? as usize);
+        let channels = read_u32()? as usize;
+        if resolution.0 == 0 || resolution.1 == 0 || resolution.2 == 0 || channels == 0 {
+            return Err(invalid_data("invalid voxel grid dimension"));
+        }
+
+        let mut corners = [0.0; 6];
+        for corner in &mut corners {
+            *corner = f32::from_bits(read_u32()?) as f64;
+        }
+
+        let count = resolution.0 * resolution.1 * resolution.2;
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            let mut sum = 0.0;
+            for _ in 0..channels {
+                sum += f32::from_bits(read_u32()?) as f64;
+            }
+            values.push(sum / channels as f64);
+        }
+
+        let bounds = AABB::from_two_points(Vec3::new(corners[0], corners[1], corners[2]), Vec3::new(corners[3], corners[4], corners[5]));
+        Ok(Self::new(resolution, bounds, values))
+    }
+
+    /// Returns maximum value in the grid, used as majorant of heterogeneous media.
+    pub fn max(&self) -> f64 {
+        self.max
+    }
+
+    /// Returns grid resolution along `x`, `y` and `z` axes.
+    pub fn resolution(&self) -> (usize, usize, usize) {
+        self.resolution
+    }
+
+    /// Returns box the grid is stretched over.
+    pub fn bounds(&self) -> AABB {
+        self.bounds
+    }
+
+    fn voxel(&self, x: usize, y: usize, z: usize) -> f64 {
+        self.values[(z * self.resolution.1 + y) * self.resolution.0 + x]
+    }
+
+    fn density(&self, point: Vec3<f64>) -> f64 {
+        let resolution = [self.resolution.0, self.resolution.1, self.resolution.2];
+        let mut cells = [(0, 0, 0.0); 3];
+
+        for axis in 0..3 {
+            let interval = self.bounds.axes[axis];
+            if point[axis] < interval.min || point[axis] > interval.max {
+                return 0.0;
+            }
+
+            // Values are stored at voxel centers.
+            let position = ((point[axis] - interval.min) / interval.len().max(f64::EPSILON) * resolution[axis] as f64 - 0.5)
+                .clamp(0.0, (resolution[axis] - 1) as f64);
+            let lower = position.floor() as usize;
+            cells[axis] = (lower, (lower + 1).min(resolution[axis] - 1), position - lower as f64);
+        }
+
+        let [(x0, x1, fx), (y0, y1, fy), (z0, z1, fz)] = cells;
+        let lerp = |a: f64, b: f64, t: f64| a * (1.0 - t) + b * t;
+        let plane = |z: usize| lerp(
+            lerp(self.voxel(x0, y0, z), self.voxel(x1, y0, z), fx),
+            lerp(self.voxel(x0, y1, z), self.voxel(x1, y1, z), fx),
+            fy
+        );
+
+        lerp(plane(z0), plane(z1), fz)
+    }
+}
+
+impl Texture for VoxelGrid {
+    fn value(&self, _: f64, _: f64, point: Vec3<f64>) -> RGB {
+        let density = self.density(point);
+        RGB(density, density, density)
+    }
+}
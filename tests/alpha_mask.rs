@@ -0,0 +1,69 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::Checker, BVHNode, Hit};
+
+fn quad(corner: Vec3<f64>, u: Vec3<f64>, v: Vec3<f64>, opacity: Checker<'static>) -> [AlphaMask<'static>; 2] {
+    [
+        AlphaMask::new(Triangle::new([corner, corner + u, corner + v], Lambertian::new(RGB(0.6, 0.4, 0.2))), opacity.clone()),
+        AlphaMask::new(Triangle::new([corner + u + v, corner + v, corner + u], Lambertian::new(RGB(0.6, 0.4, 0.2))), opacity)
+    ]
+}
+
+#[test]
+fn masked_hits_continue_traversal() {
+    let mut scene = Scene::new();
+    for half in quad(Vec3::new(-1.0, -1.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), Checker::new(0.5, 1.0, 0.0)) {
+        scene.add_object(half);
+    }
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -3.0), 1.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    let bvh = BVHNode::from_scene(scene);
+
+    // Checker cells containing (0.25, 0.25, 0.0) are opaque, cells containing (0.75, 0.25, 0.0) are holes.
+    let opaque = bvh.hit(&Ray::new(Vec3::new(0.25, 0.25, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert!((opaque.t() - 1.0).abs() < 1e-9);
+
+    let hole = bvh.hit(&Ray::new(Vec3::new(0.75, 0.25, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert!(hole.t() > 3.0);
+}
+
+#[test]
+fn partial_opacity_is_stochastic() {
+    let sphere = AlphaMask::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default())), 0.3);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+
+    let (mut front, mut back) = (0, 0);
+    for _ in 0..10_000 {
+        match sphere.hit(&ray, 0.001, f64::MAX) {
+            Some(hit_record) if hit_record.t() < 5.0 => front += 1,
+            Some(_) => back += 1,
+            None => {}
+        }
+    }
+
+    assert!((front as f64 / 10_000.0 - 0.3).abs() < 0.02, "front hits {}", front);
+    assert!((back as f64 / 10_000.0 - 0.7 * 0.3).abs() < 0.02, "back hits {}", back);
+    assert!(AlphaMask::new(sphere, 0.9).threshold(0.95).hit(&ray, 0.001, f64::MAX).is_none());
+}
+
+#[test]
+fn fence_in_front_of_spheres() {
+    let mut scene = Scene::new();
+    for half in quad(Vec3::new(-1.5, -0.5, -1.2), Vec3::new(3.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Checker::new(0.05, 1.0, 0.0)) {
+        scene.add_object(half);
+    }
+    scene.add_object(Sphere::new(Vec3::new(-0.6, 0.0, -2.0), 0.5, Lambertian::new(RGB(0.2, 0.3, 0.8))));
+    scene.add_object(Sphere::new(Vec3::new(0.6, 0.0, -2.0), 0.5, Metal::new(RGB(0.8, 0.8, 0.8), 0.1)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/alpha_mask.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,104 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::Noise, Hit, Scatter};
+
+/// Compares albedo estimated from `scatter` weights with albedo integrated from `evaluate` over the sphere.
+fn assert_integrates(material: &dyn Scatter, direction: Vec3<f64>) {
+    const SAMPLES: usize = 200_000;
+
+    let ray = Ray::new(-direction, direction);
+    let hit_record = hit_record(&ray, true);
+
+    let sampled = (0..SAMPLES)
+        .filter_map(|_| material.scatter(&ray, &hit_record))
+        .map(|(_, color)| color.g())
+        .sum::<f64>() / SAMPLES as f64;
+
+    let (mut integrated, mut pdf_integral) = (0.0, 0.0);
+    for _ in 0..SAMPLES {
+        let (value, pdf) = material.evaluate(&ray, &hit_record, Vec3::random_unit_vector()).unwrap();
+        integrated += value.g() * 4.0 * std::f64::consts::PI / SAMPLES as f64;
+        pdf_integral += pdf * 4.0 * std::f64::consts::PI / SAMPLES as f64;
+    }
+
+    assert!((sampled - integrated).abs() < 0.05, "sampled albedo {} differs from integrated {}", sampled, integrated);
+    assert!(pdf_integral < 1.05, "pdf integrates to {}", pdf_integral);
+}
+
+#[test]
+fn mix_and_layered_integrate() {
+    let direction = Vec3::new(0.5, 0.0, -1.0).normalize();
+    assert_integrates(&Mix::new(Lambertian::new(RGB(0.8, 0.8, 0.8)), Conductor::gold(0.5), 0.3), direction);
+    assert_integrates(&Layered::new(Conductor::new(RGB(1.5, 1.5, 1.5), RGB(0.0, 0.0, 0.0), 0.3), Lambertian::new(RGB(0.5, 0.5, 0.5))), direction);
+}
+
+#[test]
+fn layered_reflects_more_at_grazing_angles() {
+    let coated = Layered::new(Metal::new(RGB(1.0, 1.0, 1.0), 0.0), Lambertian::new(RGB(0.0, 0.0, 0.0)));
+    let reflectance = |direction: Vec3<f64>| {
+        let ray = Ray::new(-direction, direction);
+        let hit_record = hit_record(&ray, true);
+        (0..10_000).filter_map(|_| coated.scatter(&ray, &hit_record)).map(|(_, color)| color.g()).sum::<f64>() / 10_000.0
+    };
+
+    let normal = reflectance(Vec3::new(0.0, 0.0, -1.0));
+    assert!((normal - 0.04).abs() < 0.01, "normal reflectance {}", normal);
+    assert!(reflectance(Vec3::new(1.0, 0.0, -0.05).normalize()) > 0.5);
+}
+
+#[test]
+fn two_sided_uses_back_material() {
+    let material = TwoSided::new(Lambertian::new(RGB(1.0, 0.0, 0.0)), DiffuseLight::new(RGB(0.0, 0.0, 2.0)));
+    let direction = Vec3::new(0.0, 0.0, -1.0);
+    let ray = Ray::new(-direction, direction);
+
+    let back = hit_record(&ray, false);
+    assert!(!back.front_face());
+    assert_eq!(material.emit(&ray, &back), RGB(0.0, 0.0, 2.0));
+    assert!(material.scatter(&ray, &back).is_none());
+
+    let front = hit_record(&ray, true);
+    assert_eq!(material.emit(&ray, &front), RGB(0.0, 0.0, 0.0));
+    assert!(material.scatter(&ray, &front).is_some());
+}
+
+#[test]
+fn mix_light_power_follows_amount() {
+    let light = || DiffuseLight::new(RGB(4.0, 4.0, 4.0));
+    let dim = Mix::new(light(), Lambertian::new(RGB(0.5, 0.5, 0.5)), 0.9);
+    assert!((dim.emission().g() - 0.4).abs() < 1e-9);
+
+    let quad = Quad::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), dim);
+    let bright = Quad::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Mix::new(Lambertian::new(RGB(0.5, 0.5, 0.5)), light(), 0.9));
+    assert!((quad.power() - 0.4 * std::f64::consts::PI * quad.area()).abs() < 1e-9);
+    assert!((bright.power() - 3.6 * std::f64::consts::PI * bright.area()).abs() < 1e-9);
+}
+
+#[test]
+fn blended_materials() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+    let paint = Mix::new(Lambertian::new(RGB(0.7, 0.1, 0.1)), Conductor::aluminum(0.2), Noise::perlin(6.0));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.1, 0.0, -2.0), 0.5, paint));
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -2.0), 0.5, Layered::new(Metal::new(RGB(1.0, 1.0, 1.0), 0.02), Lambertian::new(RGB(0.1, 0.2, 0.6)))));
+    scene.add_object(Triangle::new([Vec3::new(0.7, -0.5, -2.0), Vec3::new(1.7, -0.5, -2.4), Vec3::new(1.2, 0.6, -2.2)], TwoSided::new(Lambertian::new(RGB(0.9, 0.9, 0.2)), Lambertian::new(RGB(0.2, 0.9, 0.2)))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/blended.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -1,6 +1,58 @@
 pub use rayimg::{Camera, Renderer, math::*, RGB, materials::*, shapes::*, HitRecord, Scene, P3ImageWriter};
+use rayimg::Hit;
 
 pub const ASPECT_RATIO: f64 = 16.0 / 9.0;
 pub const WIDTH: usize = 400;
 pub const HEIGHT: usize = (WIDTH as f64 / ASPECT_RATIO) as usize;
-pub const BOUNDS: (usize, usize) = (WIDTH, HEIGHT);
\ No newline at end of file
+pub const BOUNDS: (usize, usize) = (WIDTH, HEIGHT);
+/// Returns path of temporary file `name`, unique for the test process.
+#[allow(dead_code)]
+pub fn temp_path(name: &str) -> std::path::PathBuf {
+    std::env::temp_dir().join(format!("rayimg_{}_{}", std::process::id(), name))
+}
+
+/// Returns hit record of `ray` at the origin, with outward normal `(0, 0, 1)`, or `(0, 0, -1)` if not `front_face`.
+#[allow(dead_code)]
+pub fn hit_record(ray: &Ray, front_face: bool) -> HitRecord<'static> {
+    let mut hit_record = HitRecord::new(1.0, Vec3::new(0.0, 0.0, 0.0));
+    hit_record.set_face_normal(ray, if front_face { Vec3::new(0.0, 0.0, 1.0) } else { Vec3::new(0.0, 0.0, -1.0) });
+    hit_record
+}
+
+/// Checks hits of rays from `rays` against implicit surface `f` (negative inside), which also gives outward normals by gradient.
+/// Hit points must lie within `tolerance` of the surface, and no crossing may be skipped before them.
+#[allow(dead_code)]
+pub fn assert_surface(shape: &impl Hit, rays: impl Fn() -> Ray, f: impl Fn(Vec3<f64>) -> f64, tolerance: f64) {
+    let gradient = |p: Vec3<f64>| {
+        let h = 1e-7;
+        Vec3::new(
+            f(p + Vec3::new(h, 0.0, 0.0)) - f(p - Vec3::new(h, 0.0, 0.0)),
+            f(p + Vec3::new(0.0, h, 0.0)) - f(p - Vec3::new(0.0, h, 0.0)),
+            f(p + Vec3::new(0.0, 0.0, h)) - f(p - Vec3::new(0.0, 0.0, h))
+        ).normalize()
+    };
+
+    let mut hits = 0;
+    for _ in 0..2000 {
+        let ray = rays();
+        let Some(hit_record) = shape.hit(&ray, 0.001, f64::MAX) else { continue };
+        hits += 1;
+
+        let point = hit_record.point();
+        assert!(f(point).abs() < tolerance, "point {:?} is off the surface", point);
+        let bounding = shape.bounding();
+        assert!((0..3).all(|axis| bounding.axes[axis].min - 1e-6 <= point[axis] && point[axis] <= bounding.axes[axis].max + 1e-6));
+
+        let inside = f(ray.origin()) < 0.0;
+        let outward = if hit_record.front_face() { hit_record.geometric_normal() } else { -hit_record.geometric_normal() };
+        assert!((outward - gradient(point)).len() < 1e-4, "normal {:?} at {:?}", outward, point);
+        assert_eq!(hit_record.front_face(), !inside);
+
+        for step in 1..200 {
+            let value = f(ray.trace(hit_record.t() * step as f64 / 200.0));
+            assert!((value < 0.0) == inside || value.abs() < tolerance, "missed crossing before {:?}", point);
+        }
+    }
+
+    assert!(hits > 200);
+}
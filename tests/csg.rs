@@ -0,0 +1,135 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit, Solid};
+
+fn gray() -> Lambertian<'static> {
+    Lambertian::new(RGB(0.5, 0.5, 0.5))
+}
+
+fn cube() -> Cuboid<'static> {
+    Cuboid::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0), gray())
+}
+
+fn sphere() -> Sphere<'static> {
+    Sphere::new(Vec3::new(0.5, 0.5, 0.5), 1.0, gray())
+}
+
+fn cube_distance(p: Vec3<f64>) -> f64 {
+    p.x.abs().max(p.y.abs()).max(p.z.abs()) - 1.0
+}
+
+fn sphere_distance(p: Vec3<f64>) -> f64 {
+    (p - Vec3::new(0.5, 0.5, 0.5)).len() - 1.0
+}
+
+fn random_ray() -> Ray {
+    let origin = Vec3::random_unit_vector() * 4.0;
+    Ray::new(origin, Vec3::random_in_unit_sphere() * 1.5 - origin)
+}
+
+#[test]
+fn boolean_operations() {
+    assert_surface(&Csg::union(cube(), sphere()), random_ray, |p| cube_distance(p).min(sphere_distance(p)), 1e-9);
+    assert_surface(&Csg::intersection(cube(), sphere()), random_ray, |p| cube_distance(p).max(sphere_distance(p)), 1e-9);
+    assert_surface(&Csg::difference(cube(), sphere()), random_ray, |p| cube_distance(p).max(-sphere_distance(p)), 1e-9);
+    assert_surface(&Csg::difference(sphere(), cube()), random_ray, |p| sphere_distance(p).max(-cube_distance(p)), 1e-9);
+}
+
+#[test]
+fn crossings_from_inside() {
+    let shell = Csg::difference(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 2.0, gray()), Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, gray()));
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+
+    let crossings = shell.crossings(&ray, 0.001);
+    assert_eq!(crossings.iter().map(|hit_record| (hit_record.t(), hit_record.front_face())).collect::<Vec<_>>(), vec![(1.0, true), (2.0, false)]);
+    assert_eq!(crossings[0].normal(), Vec3::new(-1.0, 0.0, 0.0));
+
+    // Starting inside the shell, the ray leaves it first.
+    let crossings = shell.crossings(&ray, 1.5);
+    assert_eq!(crossings.len(), 1);
+    assert!(!crossings[0].front_face());
+
+    // Intersection with a shape the ray is inside of.
+    let cap = Csg::intersection(shell, Cuboid::new(Vec3::new(-3.0, -3.0, -3.0), Vec3::new(1.5, 3.0, 3.0), gray()));
+    let hit_record = cap.hit(&Ray::new(Vec3::new(5.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert_eq!(hit_record.t(), 3.5);
+    assert_eq!(hit_record.normal(), Vec3::new(1.0, 0.0, 0.0));
+}
+
+#[test]
+fn open_quadrics_are_empty() {
+    let cylinder = || Cylinder::new(Vec3::new(0.0, -1.0, 0.0), 0.5, 2.0, gray());
+    let ray = Ray::new(Vec3::new(-3.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0));
+    assert_eq!(cylinder().capped(true).crossings(&ray, 0.001).len(), 2);
+    assert!(cylinder().crossings(&ray, 0.001).is_empty());
+    assert!(cylinder().capped(true).sweep(180.0).crossings(&ray, 0.001).is_empty());
+    assert!(Cone::new(Vec3::new(0.0, -1.0, 0.0), 0.5, 2.0, gray()).crossings(&ray, 0.001).is_empty());
+    assert!(Paraboloid::new(Vec3::new(0.0, -1.0, 0.0), 0.5, 2.0, gray()).capped(true).sweep(90.0).crossings(&ray, 0.001).is_empty());
+
+    // Union with open shape leaves the other one alone.
+    let union = Csg::union(sphere(), cylinder());
+    let hit_record = union.hit(&Ray::new(Vec3::new(0.5, 0.5, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert_eq!(hit_record.point(), Vec3::new(0.5, 0.5, 1.5));
+    assert!(union.hit(&Ray::new(Vec3::new(-3.0, -0.8, 0.0), Vec3::new(1.0, 0.0, 0.0)), 0.001, f64::MAX).is_none());
+}
+
+#[test]
+fn csg_with_mesh() {
+    // Cube made of triangles with outward winding, in hierarchy.
+    let corner = |i: usize| Vec3::new(if i & 1 == 0 { -1.0 } else { 1.0 }, if i & 2 == 0 { -1.0 } else { 1.0 }, if i & 4 == 0 { -1.0 } else { 1.0 });
+    let faces = [[0, 2, 3, 1], [4, 5, 7, 6], [0, 1, 5, 4], [2, 6, 7, 3], [0, 4, 6, 2], [1, 3, 7, 5]];
+    let mut mesh = Scene::new();
+    for [a, b, c, d] in faces {
+        mesh.add_object(Triangle::new([corner(a), corner(b), corner(c)], gray()));
+        mesh.add_object(Triangle::new([corner(a), corner(c), corner(d)], gray()));
+    }
+
+    let from_mesh = Csg::difference(Watertight::new(BVHNode::from_scene(mesh)), sphere());
+    let from_cuboid = Csg::difference(cube(), sphere());
+    for _ in 0..2000 {
+        let origin = Vec3::random_unit_vector() * 4.0;
+        let ray = Ray::new(origin, Vec3::<f64>::random_in_unit_sphere() - origin);
+
+        let (mesh_hit, cuboid_hit) = (from_mesh.hit(&ray, 0.001, f64::MAX), from_cuboid.hit(&ray, 0.001, f64::MAX));
+        assert_eq!(mesh_hit.is_some(), cuboid_hit.is_some());
+        if let (Some(mesh_hit), Some(cuboid_hit)) = (mesh_hit, cuboid_hit) {
+            assert!((mesh_hit.t() - cuboid_hit.t()).abs() < 1e-9);
+            assert!((mesh_hit.normal() - cuboid_hit.normal()).len() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn csg_shapes() {
+    let red = || Lambertian::new(RGB(0.8, 0.2, 0.2));
+    let drill = |axis: Vec3<f64>| Transformed::new(
+        Cylinder::new(Vec3::new(0.0, -1.0, 0.0), 0.25, 2.0, Lambertian::new(RGB(0.2, 0.3, 0.8))).capped(true),
+        Transform::new().rotate(axis, 90.0)
+    );
+
+    let rounded = Csg::intersection(
+        Cuboid::new(Vec3::new(-0.5, -0.5, -0.5), Vec3::new(0.5, 0.5, 0.5), red()),
+        Sphere::new(Vec3::new(0.0, 0.0, 0.0), 0.68, Lambertian::new(RGB(0.2, 0.7, 0.3)))
+    );
+    let drilled = Csg::difference(rounded, Csg::union(Csg::union(drill(Vec3::new(1.0, 0.0, 0.0)), drill(Vec3::new(0.0, 0.0, 1.0))), drill(Vec3::new(0.0, 1.0, 0.0))));
+
+    let mut scene = Scene::new();
+    scene.add_object(Transformed::new(drilled, Transform::new().rotate(Vec3::new(0.0, 1.0, 0.0), 30.0).rotate(Vec3::new(1.0, 0.0, 0.0), 20.0).translate(Vec3::new(-0.6, 0.0, -1.8))));
+    scene.add_object(Csg::difference(
+        Sphere::new(Vec3::new(0.7, 0.0, -1.8), 0.5, Metal::new(RGB(0.8, 0.7, 0.5), 0.1)),
+        Sphere::new(Vec3::new(0.7, 0.3, -1.4), 0.45, red())
+    ));
+    scene.add_object(Plane::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(0.0, 1.0, 0.0), gray()));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/csg.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,104 @@
+mod configuration;
+use configuration::*;
+use rayimg::{backgrounds::EnvironmentMap, Background};
+
+const WIDTH_MAP: usize = 16;
+const HEIGHT_MAP: usize = 8;
+
+fn rgbe(color: [f64; 3]) -> [u8; 4] {
+    let max = color[0].max(color[1]).max(color[2]);
+    if max < 1e-32 {
+        return [0; 4];
+    }
+
+    let exponent = max.log2().floor() as i32 + 1;
+    let scale = 256.0 / 2.0f64.powi(exponent);
+    [(color[0] * scale) as u8, (color[1] * scale) as u8, (color[2] * scale) as u8, (exponent + 128) as u8]
+}
+
+fn map_color(x: usize, y: usize) -> [f64; 3] {
+    if (x, y) == (3, 2) {
+        [64.0, 32.0, 16.0]
+    } else {
+        [0.25, 0.5, 0.75 + y as f64 / 8.0]
+    }
+}
+
+fn hdr_file(run_length_encoded: bool) -> Vec<u8> {
+    let mut file = format!("#?RADIANCE\nFORMAT=32-bit_rle_rgbe\n\n-Y {} +X {}\n", HEIGHT_MAP, WIDTH_MAP).into_bytes();
+    for y in 0..HEIGHT_MAP {
+        let scanline = (0..WIDTH_MAP).map(|x| rgbe(map_color(x, y))).collect::<Vec<[u8; 4]>>();
+        if run_length_encoded {
+            file.extend([2, 2, 0, WIDTH_MAP as u8]);
+            for channel in 0..4 {
+                // First three pixels as literal dump, then single run and the rest as literals.
+                file.push(3);
+                file.extend(scanline[..3].iter().map(|pixel| pixel[channel]));
+                file.extend([129, scanline[3][channel]]);
+                file.push((WIDTH_MAP - 4) as u8);
+                file.extend(scanline[4..].iter().map(|pixel| pixel[channel]));
+            }
+        } else {
+            file.extend(scanline.iter().flatten());
+        }
+    }
+
+    file
+}
+
+#[test]
+fn read_hdr() {
+    let flat = EnvironmentMap::from_reader(&hdr_file(false)[..]).expect("Failed to read flat file");
+    let encoded = EnvironmentMap::from_reader(&hdr_file(true)[..]).expect("Failed to read run-length encoded file");
+    assert_eq!(flat.bounds(), (WIDTH_MAP, HEIGHT_MAP));
+    assert_eq!(encoded.bounds(), (WIDTH_MAP, HEIGHT_MAP));
+
+    for _ in 0..1000 {
+        let ray = Ray::new(Vec3::default(), Vec3::random_in_unit_sphere());
+        assert_eq!(flat.color(&ray), encoded.color(&ray));
+    }
+
+    assert!(EnvironmentMap::from_reader(&b"P3\n1 1\n255\n"[..]).is_err());
+    assert!(EnvironmentMap::from_reader(&hdr_file(true)[..100]).is_err());
+}
+
+#[test]
+fn importance_sampling_estimates_irradiance() {
+    let map = EnvironmentMap::from_reader(&hdr_file(true)[..]).unwrap().rotation(30.0);
+
+    let mut expected = 0.0;
+    for y in 0..HEIGHT_MAP {
+        let solid_angle = 2.0 * std::f64::consts::PI / WIDTH_MAP as f64 * ((std::f64::consts::PI * y as f64 / HEIGHT_MAP as f64).cos() - (std::f64::consts::PI * (y + 1) as f64 / HEIGHT_MAP as f64).cos());
+        for x in 0..WIDTH_MAP {
+            let color = map_color(x, y);
+            expected += RGB(color[0], color[1], color[2]).luminance() * solid_angle;
+        }
+    }
+
+    const SAMPLES: usize = 100000;
+    let mut estimated = 0.0;
+    for _ in 0..SAMPLES {
+        let direction = map.random_direction();
+        estimated += map.color(&Ray::new(Vec3::default(), direction)).luminance() / map.pdf_value(direction);
+    }
+    estimated /= SAMPLES as f64;
+
+    assert!((estimated - expected).abs() < 0.02 * expected, "estimated {} expected {}", estimated, expected);
+}
+
+#[test]
+fn environment_lighting() {
+    let map = EnvironmentMap::from_reader(&hdr_file(true)[..]).unwrap().intensity(0.5);
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -1.0), 0.5, Lambertian::new(RGB(0.8, 0.8, 0.8))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .background(map)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/environment_map.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,65 @@
+mod configuration;
+use configuration::*;
+use rayimg::Scatter;
+
+#[test]
+fn dispersion_splits_white_light() {
+    const SAMPLES: usize = 100_000;
+
+    let prism = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).dispersion(Dispersion::DIAMOND);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.6, 0.0, -0.8));
+    let mut hit_record = HitRecord::new(1.0, Vec3::new(0.6, 0.0, 0.2));
+    hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+
+    let mut average = RGB::default();
+    let (mut violet, mut red) = (Vec3::default(), Vec3::default());
+    for _ in 0..SAMPLES {
+        let (scattered, color) = prism.scatter(&ray, &hit_record).unwrap();
+        let wavelength = scattered.wavelength().expect("Dispersion must choose wavelength");
+        average += color / SAMPLES as f64;
+
+        if scattered.direction().z < 0.0 && wavelength < 420.0 {
+            violet = scattered.direction().normalize();
+        } else if scattered.direction().z < 0.0 && wavelength > 700.0 {
+            red = scattered.direction().normalize();
+        }
+    }
+
+    // Shorter wavelengths are bent more towards the normal.
+    assert!(violet.x < red.x);
+    for channel in [average.r(), average.g(), average.b()] {
+        assert!((channel - 1.0).abs() < 0.03, "average color {:?}", average);
+    }
+
+    // Wavelength chosen earlier is kept without tinting.
+    let (scattered, color) = prism.scatter(&ray.with_wavelength(500.0), &hit_record).unwrap();
+    assert_eq!((scattered.wavelength(), color), (Some(500.0), RGB(1.0, 1.0, 1.0)));
+}
+
+#[test]
+fn tinted_and_dispersive_glass() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.1, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).absorption(RGB(2.0, 0.3, 1.0))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).dispersion(Dispersion::DIAMOND)));
+    scene.add_object(Sphere::new(Vec3::new(1.1, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.2).dispersion(Dispersion::BK7).absorption(RGB(0.2, 0.2, 1.5))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/glass.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,120 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit};
+
+#[test]
+fn matches_triangle_mesh() {
+    let (nx, nz) = (7, 5);
+    let heights: Vec<f64> = (0..nx * nz).map(|_| Vec3::<f64>::random_in_unit_segment().x.abs()).collect();
+    let (scale, offset) = (Vec3::new(3.0, 0.8, 2.0), Vec3::new(-1.0, -0.2, -1.0));
+    let heightfield = Heightfield::new((nx, nz), heights.clone(), Lambertian::new(RGB::default())).scale(scale).offset(offset);
+
+    let vertex = |x: usize, z: usize| offset + Vec3::new(x as f64 * scale.x / (nx - 1) as f64, heights[z * nx + x] * scale.y, z as f64 * scale.z / (nz - 1) as f64);
+    let mut mesh = Scene::new();
+    for z in 0..nz - 1 {
+        for x in 0..nx - 1 {
+            mesh.add_object(Triangle::new([vertex(x, z), vertex(x + 1, z + 1), vertex(x + 1, z)], Lambertian::new(RGB::default())));
+            mesh.add_object(Triangle::new([vertex(x, z), vertex(x, z + 1), vertex(x + 1, z + 1)], Lambertian::new(RGB::default())));
+        }
+    }
+    let mesh = BVHNode::from_scene(mesh);
+
+    let mut hits = 0;
+    for _ in 0..5000 {
+        let origin = Vec3::random_unit_vector() * 4.0;
+        let ray = Ray::new(origin, Vec3::<f64>::random_in_unit_sphere() * 1.5 - origin);
+
+        let (expected, actual) = (mesh.hit(&ray, 0.001, f64::MAX), heightfield.hit(&ray, 0.001, f64::MAX));
+        assert_eq!(expected.is_some(), actual.is_some(), "{:?}", ray.direction());
+        if let (Some(expected), Some(actual)) = (expected, actual) {
+            hits += 1;
+            assert!((expected.t() - actual.t()).abs() < 1e-9);
+            assert!((expected.geometric_normal() - actual.geometric_normal()).len() < 1e-9);
+            assert_eq!(expected.front_face(), actual.front_face());
+        }
+    }
+
+    assert!(hits > 500);
+}
+
+#[test]
+fn interpolated_normals_and_uvs() {
+    // Bump in the middle of flat ground.
+    let heights = vec![0.0, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 0.0];
+    let heightfield = Heightfield::new((3, 3), heights, Lambertian::new(RGB::default())).scale(Vec3::new(2.0, 1.0, 2.0));
+
+    let top = heightfield.hit(&Ray::new(Vec3::new(1.0, 5.0, 1.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!((top.point().y - 1.0).abs() < 1e-9);
+    assert!((top.normal() - Vec3::new(0.0, 1.0, 0.0)).len() < 1e-9);
+    assert!((top.uv().0 - 0.5).abs() < 1e-9 && (top.uv().1 - 0.5).abs() < 1e-9);
+
+    // Slope is flat, but shading normal turns smoothly towards the top.
+    let slope = heightfield.hit(&Ray::new(Vec3::new(0.75, 5.0, 1.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    let geometric = slope.geometric_normal();
+    assert!(slope.normal().x > geometric.x && slope.normal().x < 0.0);
+
+    // Ray from below sees back face.
+    let below = heightfield.hit(&Ray::new(Vec3::new(1.0, -1.0, 1.0), Vec3::new(0.0, 1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!(!below.front_face());
+}
+
+#[test]
+#[should_panic(expected = "Heightfield scale must be positive")]
+fn flat_scale_is_rejected() {
+    let _ = Heightfield::new((2, 2), vec![0.0; 4], Lambertian::new(RGB::default())).scale(Vec3::new(1.0, 0.0, 1.0));
+}
+
+#[test]
+fn load_heightmaps() {
+    let pgm = temp_path("heights.pgm");
+    std::fs::write(&pgm, "P2\n3 2\n4\n0 2 4\n0 2 4\n").unwrap();
+    let ramp = Heightfield::load(&pgm, Lambertian::new(RGB::default())).expect("Failed to load heightmap").scale(Vec3::new(2.0, 1.0, 1.0));
+    let hit_record = ramp.hit(&Ray::new(Vec3::new(1.5, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!((hit_record.point().y - 0.75).abs() < 1e-9);
+    std::fs::remove_file(pgm).unwrap();
+
+    // 16-bit values keep fine steps of DEM data.
+    let png = temp_path("heights.png");
+    let mut encoder = png::Encoder::new(std::fs::File::create(&png).unwrap(), 2, 2);
+    encoder.set_color(png::ColorType::Grayscale);
+    encoder.set_depth(png::BitDepth::Sixteen);
+    encoder.write_header().unwrap().write_image_data(&[0, 0, 0, 1, 0, 0, 0, 1]).unwrap();
+    let steps = Heightfield::load(&png, Lambertian::new(RGB::default())).expect("Failed to load heightmap").scale(Vec3::new(1.0, 65535.0, 1.0));
+    let hit_record = steps.hit(&Ray::new(Vec3::new(1.0, 5.0, 0.5), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!((hit_record.point().y - 1.0).abs() < 1e-6);
+    std::fs::remove_file(png).unwrap();
+
+    let small = temp_path("small.pgm");
+    std::fs::write(&small, "P2\n1 2\n4\n0\n2\n").unwrap();
+    assert!(Heightfield::load(&small, Lambertian::new(RGB::default())).is_err());
+    std::fs::remove_file(small).unwrap();
+}
+
+#[test]
+fn terrain() {
+    let size = 200;
+    let heights = (0..size * size).map(|index| {
+        let (x, z) = ((index % size) as f64 / size as f64, (index / size) as f64 / size as f64);
+        let ridges = (x * 9.0).sin() * (z * 7.0 + 1.0).cos() * 0.3 + (x * 23.0 + z * 17.0).sin() * 0.08;
+        (ridges + 0.5) * (1.0 - ((x - 0.5).powi(2) + (z - 0.5).powi(2)) * 2.0)
+    }).collect();
+
+    let mut scene = Scene::new();
+    scene.add_object(Heightfield::new((size, size), heights, Lambertian::new(RGB(0.45, 0.55, 0.3)))
+        .scale(Vec3::new(4.0, 0.8, 4.0))
+        .offset(Vec3::new(-2.0, -0.8, -4.5)));
+    scene.add_object(Plane::new(Vec3::new(0.0, -0.7, 0.0), Vec3::new(0.0, 1.0, 0.0), Metal::new(RGB(0.3, 0.4, 0.6), 0.05)));
+
+    let camera = Camera::new().position(Vec3::new(0.0, 0.8, 0.5)).target(Vec3::new(0.0, -0.3, -2.5)).build();
+    let renderer = Renderer::new(BVHNode::from_scene(scene), camera)
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/heightfield.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
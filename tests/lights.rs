@@ -0,0 +1,80 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit};
+
+#[test]
+fn light_pdf_covers_samples() {
+    let origin = Vec3::new(0.0, 0.0, 0.0);
+    let sphere_light = Sphere::new(Vec3::new(0.0, 3.0, -2.0), 0.5, DiffuseLight::new(RGB(4.0, 4.0, 4.0)));
+    let triangle_light = Triangle::new([Vec3::new(-1.0, 2.0, -1.0), Vec3::new(1.0, 2.0, -1.0), Vec3::new(0.0, 2.0, -2.0)], DiffuseLight::new(RGB(4.0, 4.0, 4.0)));
+
+    for _ in 0..1000 {
+        assert!(sphere_light.pdf_value(origin, sphere_light.random_direction(origin)) > 0.0);
+        assert!(triangle_light.pdf_value(origin, triangle_light.random_direction(origin)) > 0.0);
+    }
+
+    assert_eq!(sphere_light.pdf_value(origin, Vec3::new(0.0, -1.0, 0.0)), 0.0);
+    assert_eq!(triangle_light.pdf_value(origin, Vec3::new(0.0, -1.0, 0.0)), 0.0);
+}
+
+#[test]
+fn sphere_and_triangle_lights() {
+    let sphere_light = Sphere::new(Vec3::new(-0.8, 0.6, -1.5), 0.2, DiffuseLight::new(RGB(8.0, 6.0, 4.0)));
+    let triangle_light = Triangle::new([Vec3::new(0.4, 1.0, -1.0), Vec3::new(0.8, 1.0, -2.0), Vec3::new(1.2, 1.0, -1.0)], DiffuseLight::new(RGB(4.0, 6.0, 8.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -1.5), 0.5, Lambertian::new(RGB(0.8, 0.8, 0.8))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(sphere_light.clone());
+    scene.add_object(triangle_light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(sphere_light);
+    lights.add_object(triangle_light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/lights.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
+
+#[test]
+fn light_power() {
+    let dim_light = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, DiffuseLight::new(RGB(1.0, 1.0, 1.0)));
+    let bright_light = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, DiffuseLight::new(RGB(4.0, 4.0, 4.0)));
+    let not_light = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB(1.0, 1.0, 1.0)));
+
+    assert!((bright_light.power() - 4.0 * dim_light.power()).abs() < 1e-9);
+    assert_eq!(not_light.power(), 0.0);
+}
+
+#[test]
+fn many_lights() {
+    let mut scene = Scene::new();
+    let mut lights = Scene::new();
+
+    for a in -8..8 {
+        for b in -8..8 {
+            let center = Vec3::new(a as f64 * 0.25, -0.45, -1.5 + b as f64 * 0.25);
+            let emission = if (a + b) % 5 == 0 { RGB(20.0, 12.0, 4.0) } else { RGB(0.5, 1.0, 2.0) };
+            let light = Sphere::new(center, 0.03, DiffuseLight::new(emission));
+            scene.add_object(light.clone());
+            lights.add_object(light);
+        }
+    }
+
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -1.5), 0.4, Lambertian::new(RGB(0.8, 0.8, 0.8))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(Triangle::new([Vec3::new(-1.0, -0.5, -3.0), Vec3::new(1.0, -0.5, -3.0), Vec3::new(0.0, 1.0, -3.0)], Lambertian::new(RGB(0.8, 0.3, 0.3))));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/many_lights.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
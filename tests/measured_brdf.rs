@@ -0,0 +1,113 @@
+mod configuration;
+use configuration::*;
+use rayimg::Scatter;
+
+use std::f64::consts::{FRAC_PI_2, PI};
+
+/// Table of glossy plastic: diffuse base with specular peak around the half vector.
+fn glossy(color: RGB) -> MeasuredBRDF {
+    let resolution = (90, 90, 180);
+    let values = (0..resolution.0 * resolution.1 * resolution.2).map(|index| {
+        let position = (index / (resolution.1 * resolution.2)) as f64 + 0.5;
+        let theta_half = (position / resolution.0 as f64).powi(2) * FRAC_PI_2;
+        color / PI + RGB(1.0, 1.0, 1.0) * (2.0 * (-(theta_half / 0.15).powi(2)).exp())
+    }).collect();
+
+    MeasuredBRDF::new(resolution, values)
+}
+
+#[test]
+fn measured_brdf_is_consistent() {
+    const SAMPLES: usize = 200_000;
+
+    let brdf = glossy(RGB(0.3, 0.3, 0.3));
+    let direction = Vec3::new(0.5, 0.2, -1.0).normalize();
+    let ray = Ray::new(-direction, direction);
+    let hit_record = hit_record(&ray, true);
+
+    let mut sampled = 0.0;
+    for _ in 0..SAMPLES {
+        let Some((scattered, color)) = brdf.scatter(&ray, &hit_record) else {
+            continue;
+        };
+
+        let (value, pdf) = brdf.evaluate(&ray, &hit_record, scattered.direction()).unwrap();
+        assert!((value.g() / pdf - color.g()).abs() < 1e-6 * color.g().max(1.0));
+        sampled += color.g() / SAMPLES as f64;
+    }
+
+    let (mut integrated, mut pdf_integral) = (0.0, 0.0);
+    for _ in 0..SAMPLES {
+        let (value, pdf) = brdf.evaluate(&ray, &hit_record, Vec3::random_unit_vector()).unwrap();
+        integrated += value.g() * 4.0 * PI / SAMPLES as f64;
+        pdf_integral += pdf * 4.0 * PI / SAMPLES as f64;
+    }
+
+    assert!((sampled - integrated).abs() < 0.05, "sampled albedo {} differs from integrated {}", sampled, integrated);
+    assert!((pdf_integral - 1.0).abs() < 0.05, "pdf integrates to {}", pdf_integral);
+}
+
+#[test]
+fn load_merl_binary() {
+    let path = temp_path("brdf.binary");
+    let resolution = [2i32, 2, 4];
+
+    let mut bytes = resolution.iter().flat_map(|dimension| dimension.to_le_bytes()).collect::<Vec<u8>>();
+    for channel in 1..=3 {
+        for _ in 0..16 {
+            bytes.extend((channel as f64 * 1500.0).to_le_bytes());
+        }
+    }
+    std::fs::write(&path, &bytes).unwrap();
+
+    let brdf = MeasuredBRDF::load(&path).expect("Failed to load BRDF");
+    assert_eq!(brdf.resolution(), (2, 2, 4));
+
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let (value, _) = brdf.evaluate(&ray, &hit_record(&ray, true), Vec3::new(0.0, 0.0, 1.0)).unwrap();
+    assert!((value.r() - 1.0).abs() < 1e-9 && (value.g() - 2.3).abs() < 1e-9 && (value.b() - 4.98).abs() < 1e-9);
+
+    std::fs::write(&path, &bytes[..bytes.len() - 8]).unwrap();
+    assert!(MeasuredBRDF::load(&path).is_err());
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn reject_corrupt_headers() {
+    let header = |resolution: [i32; 3]| resolution.iter().flat_map(|dimension| dimension.to_le_bytes()).collect::<Vec<u8>>();
+
+    let overflowing = MeasuredBRDF::from_reader(&header([i32::MAX; 3])[..]).err().unwrap();
+    assert_eq!(overflowing.kind(), std::io::ErrorKind::InvalidData);
+
+    // Huge table with no data fails on reading instead of allocating it.
+    let truncated = MeasuredBRDF::from_reader(&header([65536, 65536, 4096])[..]).err().unwrap();
+    assert_eq!(truncated.kind(), std::io::ErrorKind::UnexpectedEof);
+}
+
+#[test]
+fn measured_materials() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-0.6, 0.0, -2.0), 0.5, glossy(RGB(0.7, 0.1, 0.1))));
+    scene.add_object(Sphere::new(Vec3::new(0.6, 0.0, -2.0), 0.5, glossy(RGB(0.1, 0.2, 0.7))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/measured_brdf.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,129 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::*, Hit, Scatter, Texture, AABB};
+
+#[test]
+fn henyey_greenstein_phase() {
+    const SAMPLES: usize = 200_000;
+
+    let phase = HenyeyGreenstein::new(RGB(1.0, 1.0, 1.0), 0.6);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 2.0, 0.0));
+    let hit_record = HitRecord::new(1.0, Vec3::new(1.0, 2.0, 0.0));
+
+    let mut mean_cosine = 0.0;
+    for _ in 0..SAMPLES {
+        let (scattered, _) = phase.scatter(&ray, &hit_record).unwrap();
+        mean_cosine += scattered.direction().normalize().dot(&ray.direction().normalize()) / SAMPLES as f64;
+    }
+    assert!((mean_cosine - 0.6).abs() < 0.01, "mean cosine {}", mean_cosine);
+
+    let integral = (0..SAMPLES)
+        .map(|_| phase.evaluate(&ray, &hit_record, Vec3::random_unit_vector()).unwrap().1)
+        .sum::<f64>() * 4.0 * std::f64::consts::PI / SAMPLES as f64;
+    assert!((integral - 1.0).abs() < 0.03, "phase integrates to {}", integral);
+}
+
+#[test]
+fn constant_medium_transmittance() {
+    const SAMPLES: usize = 100_000;
+
+    let boundary = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default()));
+    let medium = ConstantMedium::new(boundary, 0.5, Isotropic::new(RGB(1.0, 1.0, 1.0)));
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -2.0));
+
+    let passed = (0..SAMPLES).filter(|_| medium.hit(&ray, 0.001, f64::MAX).is_none()).count();
+    let transmittance = passed as f64 / SAMPLES as f64;
+    assert!((transmittance - (-1.0f64).exp()).abs() < 0.01, "transmittance {}", transmittance);
+}
+
+#[test]
+fn smoke_and_fog() {
+    let light = Sphere::new(Vec3::new(0.0, 2.5, -1.5), 0.7, DiffuseLight::new(RGB(8.0, 8.0, 8.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(ConstantMedium::new(Sphere::new(Vec3::new(-0.6, 0.0, -1.5), 0.5, Lambertian::new(RGB::default())), 4.0, Isotropic::new(RGB(0.9, 0.9, 0.9))));
+    scene.add_object(ConstantMedium::new(Sphere::new(Vec3::new(0.6, 0.0, -1.5), 0.5, Lambertian::new(RGB::default())), 8.0, HenyeyGreenstein::new(RGB(0.2, 0.4, 0.9), 0.5)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .fog(0.15, HenyeyGreenstein::new(RGB(0.9, 0.9, 0.9), 0.3))
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/media.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
+
+#[test]
+fn heterogeneous_medium_transmittance() {
+    const SAMPLES: usize = 100_000;
+
+    let bounds = AABB::from_two_points(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 1.0, 1.0));
+    let grid = VoxelGrid::new((2, 2, 2), bounds, vec![0.25; 8]);
+    let medium = HeterogeneousMedium::new(bounds, grid, 1.0, Isotropic::new(RGB(1.0, 1.0, 1.0))).scale(2.0);
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -1.0));
+    let expected = (-1.0f64).exp();
+
+    let ratio_tracking = (0..SAMPLES).map(|_| medium.transmittance(&ray, 0.0, f64::MAX)).sum::<f64>() / SAMPLES as f64;
+    assert!((ratio_tracking - expected).abs() < 0.01, "ratio tracking transmittance {}", ratio_tracking);
+
+    let passed = (0..SAMPLES).filter(|_| medium.hit(&ray, 0.001, f64::MAX).is_none()).count();
+    let delta_tracking = passed as f64 / SAMPLES as f64;
+    assert!((delta_tracking - expected).abs() < 0.01, "delta tracking transmittance {}", delta_tracking);
+}
+
+#[test]
+fn load_voxel_grid() {
+    let mut bytes = b"VOL\x03".to_vec();
+    for value in [1u32, 2, 1, 1, 2] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+    for value in [0.0f32, 0.0, 0.0, 2.0, 1.0, 1.0, 0.2, 0.4, 0.6, 0.8] {
+        bytes.extend_from_slice(&value.to_le_bytes());
+    }
+
+    let grid = VoxelGrid::from_reader(&bytes[..]).unwrap();
+    assert_eq!(grid.resolution(), (2, 1, 1));
+    assert!((grid.max() - 0.7).abs() < 1e-6);
+    assert!((grid.value(0.0, 0.0, Vec3::new(0.25, 0.5, 0.5)).r() - 0.3).abs() < 1e-6);
+
+    assert!(VoxelGrid::from_reader(&b"VOL\x02"[..]).is_err());
+    assert!(VoxelGrid::from_reader(&bytes[..20]).is_err());
+}
+
+#[test]
+fn noise_cloud() {
+    let light = Sphere::new(Vec3::new(0.0, 2.5, -1.5), 0.7, DiffuseLight::new(RGB(8.0, 8.0, 8.0)));
+    let bounds = AABB::from_two_points(Vec3::new(-1.0, -0.3, -2.2), Vec3::new(1.0, 0.5, -1.0));
+
+    let mut scene = Scene::new();
+    scene.add_object(HeterogeneousMedium::new(bounds, Noise::turbulence(3.0, 5), 1.0, HenyeyGreenstein::new(RGB(0.95, 0.95, 0.95), 0.4)).scale(12.0));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/noise_cloud.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,132 @@
+mod configuration;
+use configuration::*;
+use rayimg::Scatter;
+
+/// Checks that weights of scattered rays are ratios of values and densities returned by `evaluate`.
+fn assert_consistent(material: &dyn Scatter, direction: Vec3<f64>, front_face: bool) {
+    let ray = Ray::new(-direction, direction);
+    let hit_record = hit_record(&ray, front_face);
+
+    let mut albedo = 0.0;
+    for _ in 0..10_000 {
+        let Some((scattered, color)) = material.scatter(&ray, &hit_record) else {
+            continue;
+        };
+
+        let (value, pdf) = material.evaluate(&ray, &hit_record, scattered.direction()).unwrap();
+        assert!(pdf > 0.0);
+        assert!((value.g() / pdf - color.g()).abs() < 1e-6 * color.g().max(1.0), "weight {} differs from {}", color.g(), value.g() / pdf);
+        albedo += color.g() / 10_000.0;
+    }
+
+    assert!(albedo > 0.5 && albedo <= 1.0 + 1e-9, "albedo {}", albedo);
+}
+
+/// Compares albedo estimated from `scatter` weights with albedo integrated from `evaluate` over the sphere.
+fn assert_integrates(material: &dyn Scatter, direction: Vec3<f64>) {
+    const SAMPLES: usize = 200_000;
+
+    let ray = Ray::new(-direction, direction);
+    let hit_record = hit_record(&ray, true);
+
+    let sampled = (0..SAMPLES)
+        .filter_map(|_| material.scatter(&ray, &hit_record))
+        .map(|(_, color)| color.g())
+        .sum::<f64>() / SAMPLES as f64;
+
+    let (mut integrated, mut pdf_integral) = (0.0, 0.0);
+    for _ in 0..SAMPLES {
+        let (value, pdf) = material.evaluate(&ray, &hit_record, Vec3::random_unit_vector()).unwrap();
+        integrated += value.g() * 4.0 * std::f64::consts::PI / SAMPLES as f64;
+        pdf_integral += pdf * 4.0 * std::f64::consts::PI / SAMPLES as f64;
+    }
+
+    assert!((sampled - integrated).abs() < 0.05, "sampled albedo {} differs from integrated {}", sampled, integrated);
+    assert!(pdf_integral < 1.05, "pdf integrates to {}", pdf_integral);
+}
+
+#[test]
+fn conductor_is_consistent() {
+    let direction = Vec3::new(0.5, 0.0, -1.0).normalize();
+    assert_consistent(&Conductor::gold(0.6), direction, true);
+    assert_consistent(&Conductor::aluminum(0.3), direction, true);
+    assert_integrates(&Conductor::copper(0.6), direction);
+}
+
+#[test]
+fn rough_dielectric_is_consistent() {
+    let glass = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.5);
+    assert_consistent(&glass, Vec3::new(0.3, 0.0, -1.0).normalize(), true);
+    assert_consistent(&glass, Vec3::new(0.3, 0.0, -1.0).normalize(), false);
+
+    let coated = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.5).thin_film(ThinFilm::new(500.0, 1.33));
+    assert_consistent(&coated, Vec3::new(0.3, 0.0, -1.0).normalize(), true);
+    assert_consistent(&coated, Vec3::new(0.3, 0.0, -1.0).normalize(), false);
+}
+
+#[test]
+fn microfacet_materials() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.65, 0.0, -2.0), 0.5, Conductor::gold(0.2)));
+    scene.add_object(Sphere::new(Vec3::new(-0.55, 0.0, -2.0), 0.5, Conductor::copper(0.4)));
+    scene.add_object(Sphere::new(Vec3::new(0.55, 0.0, -2.0), 0.5, Conductor::aluminum(0.05)));
+    scene.add_object(Sphere::new(Vec3::new(1.65, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.3)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/microfacet.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
+
+#[test]
+fn principled_is_consistent() {
+    let direction = Vec3::new(0.4, 0.0, -1.0).normalize();
+    assert_consistent(&Principled::new(RGB(0.8, 0.8, 0.8)).roughness(0.3).clearcoat(1.0), direction, true);
+    assert_consistent(&Principled::new(RGB(1.0, 0.8, 0.4)).metallic(1.0).roughness(0.5), direction, true);
+    assert_consistent(&Principled::new(RGB(1.0, 1.0, 1.0)).transmission(1.0).roughness(0.4), direction, false);
+    assert_integrates(&Principled::new(RGB(0.8, 0.8, 0.8)).roughness(0.6).sheen(1.0), direction);
+}
+
+#[test]
+fn principled_materials() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.65, 0.0, -2.0), 0.5, Principled::new(RGB(0.6, 0.05, 0.05)).roughness(0.4).clearcoat(1.0).clearcoat_roughness(0.05)));
+    scene.add_object(Sphere::new(Vec3::new(-0.55, 0.0, -2.0), 0.5, Principled::new(RGB(1.0, 0.78, 0.34)).metallic(1.0).roughness(0.35)));
+    scene.add_object(Sphere::new(Vec3::new(0.55, 0.0, -2.0), 0.5, Principled::new(RGB(0.2, 0.2, 0.6)).roughness(0.9).sheen(1.0)));
+    scene.add_object(Sphere::new(Vec3::new(1.65, 0.0, -2.0), 0.5, Principled::new(RGB(0.9, 1.0, 0.9)).transmission(1.0).roughness(0.1)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Principled::new(RGB(0.5, 0.5, 0.5)).roughness(0.8)));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/principled.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,81 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::Checker, BVHNode, Hit};
+
+#[test]
+fn unbounded_objects_stay_out_of_hierarchy() {
+    let mut scene = Scene::new();
+    scene.add_object(Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB::default())));
+    scene.add_object(Plane::new(Vec3::new(0.0, 0.0, -10.0), Vec3::new(0.0, 0.0, 1.0), Lambertian::new(RGB::default())));
+    assert!(!scene.bounding().is_bounded());
+
+    // Hierarchy of unbounded objects only.
+    let walls = BVHNode::from_scene(scene.clone());
+    assert_eq!(walls.hit(&Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, -1.0)), 0.001, f64::MAX).unwrap().t(), 1.0);
+    assert_eq!(walls.hit(&Ray::new(Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap().t(), 10.0);
+
+    // Nested scene with unbounded objects is unbounded too.
+    let mut spheres = Scene::new();
+    for x in 0..10 {
+        spheres.add_object(Sphere::new(Vec3::new(x as f64 * 3.0, 1.0, -5.0), 1.0, Lambertian::new(RGB::default())));
+    }
+    spheres.add_object(scene);
+    let bvh = BVHNode::from_scene(spheres);
+
+    assert!(!bvh.bounding().is_bounded());
+    assert_eq!(bvh.hit(&Ray::new(Vec3::new(9.0, 1.0, 0.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap().t(), 4.0);
+    assert_eq!(bvh.hit(&Ray::new(Vec3::new(1000.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0)), 0.001, f64::MAX).unwrap().t(), 5.0);
+}
+
+#[test]
+fn transformed_plane() {
+    let wall = Transformed::new(
+        Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB::default())),
+        Transform::new().rotate(Vec3::new(1.0, 0.0, 0.0), 90.0).translate(Vec3::new(0.0, 0.0, -3.0))
+    );
+
+    assert!(!wall.bounding().is_bounded());
+    let hit_record = wall.hit(&Ray::new(Vec3::new(7.0, 2.0, 0.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert!((hit_record.t() - 3.0).abs() < 1e-9);
+    assert!((hit_record.normal() - Vec3::new(0.0, 0.0, 1.0)).len() < 1e-9);
+}
+
+#[test]
+fn plane_uvs_tile() {
+    let ground = Plane::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB::default())).tile(2.5);
+    let down = |x: f64, z: f64| Ray::new(Vec3::new(x, 1.0, z), Vec3::new(0.0, -1.0, 0.0));
+
+    for _ in 0..1000 {
+        let offset = Vec3::<f64>::random_in_unit_sphere() * 100.0;
+        let hit_record = ground.hit(&down(offset.x, offset.z), 0.001, f64::MAX).unwrap();
+        let (u, v) = hit_record.uv();
+        assert!((0.0..=1.0).contains(&u) && (0.0..=1.0).contains(&v), "uv {:?}", (u, v));
+
+        // Neighbouring tile has the same coordinates.
+        let (u_next, v_next) = ground.hit(&down(offset.x + 2.5, offset.z - 5.0), 0.001, f64::MAX).unwrap().uv();
+        assert!(((u - u_next + 0.5).rem_euclid(1.0) - 0.5).abs() < 1e-9 && ((v - v_next + 0.5).rem_euclid(1.0) - 0.5).abs() < 1e-9);
+
+        let (dpdu, dpdv) = hit_record.tangents();
+        assert!((dpdu.len() - 2.5).abs() < 1e-9 && (dpdv.len() - 2.5).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn checkered_ground_plane() {
+    let mut scene = Scene::new();
+    scene.add_object(Plane::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(Checker::new(0.5, RGB(0.8, 0.8, 0.8), RGB(0.2, 0.3, 0.1)))));
+    scene.add_object(Sphere::new(Vec3::new(-0.6, 0.0, -1.5), 0.5, Lambertian::new(RGB(0.7, 0.3, 0.3))));
+    scene.add_object(Sphere::new(Vec3::new(0.6, 0.0, -1.5), 0.5, Metal::new(RGB(0.8, 0.8, 0.8), 0.05)));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/plane.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,141 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit};
+
+/// Returns solid angle of triangle seen from `origin` by formula of Van Oosterom and Strackee.
+fn triangle_solid_angle(origin: Vec3<f64>, vertices: [Vec3<f64>; 3]) -> f64 {
+    let [a, b, c] = vertices.map(|vertex| vertex - origin);
+    let (a_len, b_len, c_len) = (a.len(), b.len(), c.len());
+    let denominator = a_len * b_len * c_len + a.dot(&b) * c_len + a.dot(&c) * b_len + b.dot(&c) * a_len;
+    2.0 * a.dot(&b.cross(&c)).abs().atan2(denominator)
+}
+
+/// Returns solid angle of parallelogram spanned by `u` and `v` from `corner`, seen from `origin`.
+fn quad_solid_angle(origin: Vec3<f64>, corner: Vec3<f64>, u: Vec3<f64>, v: Vec3<f64>) -> f64 {
+    triangle_solid_angle(origin, [corner, corner + u, corner + u + v]) + triangle_solid_angle(origin, [corner, corner + u + v, corner + v])
+}
+
+/// Compares solid angle of the shape estimated by its own sampling with the exact one,
+/// which agree when `pdf_value` is density of `random_direction`.
+fn solid_angle_ratio(shape: &impl Hit, origin: Vec3<f64>, solid_angle: f64) -> f64 {
+    let samples = 1_000_000;
+    let sampled: f64 = (0..samples).map(|_| 1.0 / shape.pdf_value(origin, shape.random_direction(origin))).sum();
+    sampled / samples as f64 / solid_angle
+}
+
+#[test]
+fn planar_light_sampling() {
+    let origin = Vec3::new(0.2, -0.3, 0.0);
+    let (corner, u, v) = (Vec3::new(-1.0, 1.0, -2.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 0.5, -1.0));
+    let quad = Quad::new(corner, u, v, DiffuseLight::new(RGB(1.0, 1.0, 1.0)));
+    let (center, normal, radius) = (Vec3::new(0.0, 1.0, -1.0), Vec3::new(0.0, -1.0, 1.0), 0.8);
+    let disk = Disk::new(center, normal, radius, DiffuseLight::new(RGB(1.0, 1.0, 1.0)));
+    let (min, max) = (Vec3::new(-0.5, 1.0, -1.5), Vec3::new(0.5, 1.5, -0.5));
+    let cuboid = Cuboid::new(min, max, DiffuseLight::new(RGB(1.0, 1.0, 1.0)));
+
+    for _ in 0..1000 {
+        assert!(quad.pdf_value(origin, quad.random_direction(origin)) > 0.0);
+        assert!(disk.pdf_value(origin, disk.random_direction(origin)) > 0.0);
+        assert!(cuboid.pdf_value(origin, cuboid.random_direction(origin)) > 0.0);
+    }
+    assert_eq!(quad.pdf_value(origin, Vec3::new(0.0, -1.0, 0.0)), 0.0);
+
+    // Disk is measured as fine polygon, and cuboid by its bottom and front faces, which are the ones looking at the origin.
+    let basis = ONB::new(normal.normalize());
+    let rim = |angle: f64| center + (basis.u() * angle.cos() + basis.v() * angle.sin()) * radius;
+    let segments = 4096;
+    let disk_solid_angle: f64 = (0..segments)
+        .map(|index| std::f64::consts::TAU * index as f64 / segments as f64)
+        .map(|angle| triangle_solid_angle(origin, [center, rim(angle), rim(angle + std::f64::consts::TAU / segments as f64)]))
+        .sum();
+    let size = max - min;
+    let cuboid_solid_angle = quad_solid_angle(origin, min, Vec3::new(size.x, 0.0, 0.0), Vec3::new(0.0, 0.0, size.z))
+        + quad_solid_angle(origin, Vec3::new(min.x, min.y, max.z), Vec3::new(size.x, 0.0, 0.0), Vec3::new(0.0, size.y, 0.0));
+
+    let ratios = [
+        solid_angle_ratio(&quad, origin, quad_solid_angle(origin, corner, u, v)),
+        solid_angle_ratio(&disk, origin, disk_solid_angle),
+        solid_angle_ratio(&cuboid, origin, cuboid_solid_angle)
+    ];
+    for ratio in ratios {
+        assert!((ratio - 1.0).abs() < 0.01, "ratio {}", ratio);
+    }
+
+    assert!((quad.power() - std::f64::consts::PI * quad.area()).abs() < 1e-9);
+    assert!((cuboid.power() - std::f64::consts::PI * cuboid.area()).abs() < 1e-9);
+}
+
+#[test]
+fn cuboid_faces_point_outward() {
+    let cuboid = Cuboid::new(Vec3::new(1.0, 1.0, 1.0), Vec3::new(-1.0, -1.0, -1.0), Lambertian::new(RGB::default()));
+    let directions = [
+        Vec3::new(1.0, 0.0, 0.0), Vec3::new(-1.0, 0.0, 0.0),
+        Vec3::new(0.0, 1.0, 0.0), Vec3::new(0.0, -1.0, 0.0),
+        Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0)
+    ];
+
+    for direction in directions {
+        let outside = cuboid.hit(&Ray::new(direction * 3.0, -direction), 0.001, f64::MAX).unwrap();
+        assert!(outside.front_face());
+        assert_eq!(outside.geometric_normal(), direction);
+        assert!((outside.t() - 2.0).abs() < 1e-9);
+
+        let inside = cuboid.hit(&Ray::new(Vec3::new(0.0, 0.0, 0.0), direction), 0.001, f64::MAX).unwrap();
+        assert!(!inside.front_face());
+        assert_eq!(inside.geometric_normal(), -direction);
+    }
+}
+
+#[test]
+fn disk_uvs() {
+    let disk = Disk::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(0.0, 0.0, 1.0), 2.0, Lambertian::new(RGB::default()));
+    let ray = |x: f64, y: f64| Ray::new(Vec3::new(x, y, 1.0), Vec3::new(0.0, 0.0, -1.0));
+
+    let (u_a, v_a) = disk.hit(&ray(1.0, 0.0), 0.001, f64::MAX).unwrap().uv();
+    let (u_b, v_b) = disk.hit(&ray(0.0, 1.5), 0.001, f64::MAX).unwrap().uv();
+    assert!((v_a - 0.5).abs() < 1e-9 && (v_b - 0.75).abs() < 1e-9);
+    assert!(((u_b - u_a).rem_euclid(1.0) - 0.25).abs() < 1e-9 || ((u_a - u_b).rem_euclid(1.0) - 0.25).abs() < 1e-9);
+
+    // Tangents follow texture coordinates.
+    let hit_record = disk.hit(&ray(1.0, 0.0), 0.001, f64::MAX).unwrap();
+    let (dpdu, dpdv) = hit_record.tangents();
+    assert!((dpdu.len() - 2.0 * std::f64::consts::PI).abs() < 1e-9);
+    assert!((dpdv - Vec3::new(2.0, 0.0, 0.0)).len() < 1e-9);
+}
+
+#[test]
+fn cornell_box() {
+    let red = Lambertian::new(RGB(0.65, 0.05, 0.05));
+    let white = || Lambertian::new(RGB(0.73, 0.73, 0.73));
+    let green = Lambertian::new(RGB(0.12, 0.45, 0.15));
+    let light = Quad::new(Vec3::new(-0.3, 1.999, -0.7), Vec3::new(0.0, 0.0, -0.6), Vec3::new(0.6, 0.0, 0.0), DiffuseLight::new(RGB(15.0, 15.0, 15.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Quad::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -2.0), Vec3::new(0.0, 2.0, 0.0), red));
+    scene.add_object(Quad::new(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, 0.0, -2.0), green));
+    scene.add_object(Quad::new(Vec3::new(-1.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 0.0, -2.0), white()));
+    scene.add_object(Quad::new(Vec3::new(-1.0, 2.0, 0.0), Vec3::new(0.0, 0.0, -2.0), Vec3::new(2.0, 0.0, 0.0), white()));
+    scene.add_object(Quad::new(Vec3::new(-1.0, 0.0, -2.0), Vec3::new(2.0, 0.0, 0.0), Vec3::new(0.0, 2.0, 0.0), white()));
+    scene.add_object(Transformed::new(
+        Cuboid::new(Vec3::new(-0.3, 0.0, -0.3), Vec3::new(0.3, 1.2, 0.3), white()),
+        Transform::new().rotate(Vec3::new(0.0, 1.0, 0.0), 15.0).translate(Vec3::new(-0.35, 0.0, -1.3))
+    ));
+    scene.add_object(Transformed::new(
+        Cuboid::new(Vec3::new(-0.3, 0.0, -0.3), Vec3::new(0.3, 0.6, 0.3), white()),
+        Transform::new().rotate(Vec3::new(0.0, 1.0, 0.0), -18.0).translate(Vec3::new(0.4, 0.0, -0.8))
+    ));
+    scene.add_object(Disk::new(Vec3::new(0.4, 0.601, -0.8), Vec3::new(0.0, 1.0, 0.0), 0.2, Metal::new(RGB(0.8, 0.7, 0.3), 0.2)));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let camera = Camera::new().position(Vec3::new(0.0, 1.0, 2.4)).target(Vec3::new(0.0, 1.0, -1.0)).vertical_fov(40.0).build();
+    let renderer = Renderer::new(BVHNode::from_scene(scene), camera)
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/cornell_box.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
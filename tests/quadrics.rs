@@ -0,0 +1,90 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit};
+
+fn random_ray_towards(target: Vec3<f64>, distance: f64) -> Ray {
+    let origin = target + Vec3::random_unit_vector() * distance;
+    let aim = target + Vec3::random_in_unit_sphere() * distance * 0.3;
+    Ray::new(origin, aim - origin)
+}
+
+#[test]
+fn torus_surface() {
+    let center = Vec3::new(1.0, -0.5, 0.5);
+    let torus = Torus::new(center, 1.0, 0.25, Lambertian::new(RGB::default()));
+
+    assert_surface(&torus, || random_ray_towards(center, 4.0), |p| {
+        let p = p - center;
+        let rho = (p.x * p.x + p.z * p.z).sqrt();
+        ((rho - 1.0) * (rho - 1.0) + p.y * p.y).sqrt() - 0.25
+    }, 1e-6);
+}
+
+#[test]
+fn capped_quadric_surfaces() {
+    let base = Vec3::new(0.0, -0.5, 0.0);
+    let center = base + Vec3::new(0.0, 0.5, 0.0);
+    let material = || Lambertian::new(RGB::default());
+
+    // Closed shapes are intersections of the side with slab between caps.
+    let slab = |y: f64| (-y).max(y - 1.0);
+    assert_surface(&Cylinder::new(base, 0.5, 1.0, material()).capped(true), || random_ray_towards(center, 4.0), |p| {
+        let p = p - base;
+        ((p.x * p.x + p.z * p.z).sqrt() - 0.5).max(slab(p.y))
+    }, 1e-6);
+    assert_surface(&Cone::new(base, 0.5, 1.0, material()).capped(true), || random_ray_towards(center, 4.0), |p| {
+        let p = p - base;
+        ((p.x * p.x + p.z * p.z).sqrt() - 0.5 * (1.0 - p.y)).max(slab(p.y))
+    }, 1e-6);
+    assert_surface(&Paraboloid::new(base, 0.5, 1.0, material()).capped(true), || random_ray_towards(center, 4.0), |p| {
+        let p = p - base;
+        (p.x * p.x + p.z * p.z - 0.25 * p.y).max(slab(p.y))
+    }, 1e-6);
+}
+
+#[test]
+fn open_and_swept_quadrics() {
+    let cylinder = Cylinder::new(Vec3::new(0.0, 0.0, 0.0), 1.0, 1.0, Lambertian::new(RGB::default()));
+    let down = Ray::new(Vec3::new(0.0, 2.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+    assert!(cylinder.hit(&down, 0.001, f64::MAX).is_none());
+
+    // Capped cylinder hit from inside faces the ray.
+    let capped = cylinder.clone().capped(true);
+    let hit_record = capped.hit(&Ray::new(Vec3::new(0.0, 0.5, 0.0), Vec3::new(0.0, 1.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!(!hit_record.front_face());
+    assert_eq!(hit_record.normal(), Vec3::new(0.0, -1.0, 0.0));
+
+    // Quarter of a cone only covers 0 < φ < 90, i.e. x > 0 and z < 0.
+    let quarter = Cone::new(Vec3::new(0.0, 0.0, 0.0), 1.0, 1.0, Lambertian::new(RGB::default())).sweep(90.0);
+    assert!(quarter.hit(&Ray::new(Vec3::new(2.0, 0.2, -0.3), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f64::MAX).is_some());
+    assert!(quarter.hit(&Ray::new(Vec3::new(2.0, 0.2, 0.3), Vec3::new(-1.0, 0.0, 0.0)), 0.001, f64::MAX).is_none());
+
+    // Side texture coordinates wrap around the axis and rise with height.
+    let (u, v) = cylinder.hit(&Ray::new(Vec3::new(0.0, 0.75, -2.0), Vec3::new(0.0, 0.0, 1.0)), 0.001, f64::MAX).unwrap().uv();
+    assert!((u - 0.25).abs() < 1e-9 && (v - 0.75).abs() < 1e-9);
+}
+
+#[test]
+fn quadric_shapes() {
+    let mut scene = Scene::new();
+    scene.add_object(Cylinder::new(Vec3::new(-1.35, -0.5, -2.0), 0.3, 0.8, Lambertian::new(RGB(0.8, 0.3, 0.2))).capped(true));
+    scene.add_object(Cone::new(Vec3::new(-0.45, -0.5, -2.0), 0.35, 0.9, Metal::new(RGB(0.8, 0.7, 0.4), 0.2)).capped(true));
+    scene.add_object(Paraboloid::new(Vec3::new(0.45, -0.5, -2.0), 0.35, 0.8, Lambertian::new(RGB(0.2, 0.6, 0.3))).sweep(270.0));
+    scene.add_object(Transformed::new(
+        Torus::new(Vec3::new(0.0, 0.0, 0.0), 0.3, 0.1, Lambertian::new(RGB(0.2, 0.3, 0.8))),
+        Transform::new().rotate(Vec3::new(1.0, 0.0, 0.0), 60.0).translate(Vec3::new(1.35, -0.1, -2.0))
+    ));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/quadrics.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,107 @@
+mod configuration;
+use configuration::*;
+use rayimg::{distances, BVHNode, Distance, Hit, AABB};
+
+fn bounds(size: f64) -> AABB {
+    AABB::from_two_points(Vec3::new(-size, -size, -size), Vec3::new(size, size, size))
+}
+
+fn random_inside() -> bool {
+    Vec3::<f64>::random_in_unit_segment().x > 0.0
+}
+
+#[test]
+fn marched_sphere_matches_analytic() {
+    let marched = Sdf::new(distances::Sphere::new(1.0), bounds(1.5), Lambertian::new(RGB::default()));
+    let analytic = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default()));
+
+    for _ in 0..2000 {
+        let origin = if random_inside() { Vec3::random_in_unit_sphere() * 0.9 } else { Vec3::random_unit_vector() * 3.0 };
+        let ray = Ray::new(origin, Vec3::<f64>::random_in_unit_sphere() * 1.2 - origin);
+
+        let (marched_hit, analytic_hit) = (marched.hit(&ray, 0.001, f64::MAX), analytic.hit(&ray, 0.001, f64::MAX));
+        if let Some(analytic_hit) = analytic_hit {
+            // Grazing rays may slip past the surface.
+            let cosine = analytic_hit.normal().dot(&ray.direction().normalize()).abs();
+            if cosine < 0.05 {
+                continue;
+            }
+
+            let marched_hit = marched_hit.expect("marched sphere missed");
+            // Marching stops within epsilon of the surface, which is further along the ray at low angles.
+            assert!((marched_hit.point() - analytic_hit.point()).len() < 2e-4 / cosine);
+            assert!((marched_hit.point().len() - 1.0).abs() < 1e-4);
+            let exact = if marched_hit.front_face() { marched_hit.point().normalize() } else { -marched_hit.point().normalize() };
+            assert!((marched_hit.normal() - exact).len() < 1e-6);
+            assert_eq!(marched_hit.front_face(), analytic_hit.front_face());
+        } else if let Some(marched_hit) = marched_hit {
+            // Only rays passing within epsilon of the surface hit it when they shouldn't.
+            assert!((marched_hit.point().len() - 1.0).abs() < 1e-4);
+        }
+    }
+}
+
+#[test]
+fn scattered_rays_leave_surface() {
+    let sdf = Sdf::new(distances::Cuboid::new(Vec3::new(1.0, 1.0, 1.0)).round(0.2), bounds(2.0), Lambertian::new(RGB::default()));
+    let hit_record = sdf.hit(&Ray::new(Vec3::new(0.3, 0.2, 5.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert!((hit_record.point().z - 1.2).abs() < 1e-4);
+
+    // Reflected ray leaves the surface, refracted ray finds the other side.
+    let reflected = Ray::new(hit_record.point(), Vec3::new(0.3, 0.0, 1.0));
+    assert!(sdf.hit(&reflected, 0.001, f64::MAX).is_none());
+
+    let refracted = Ray::new(hit_record.point(), Vec3::new(0.0, 0.0, -1.0));
+    let exit = sdf.hit(&refracted, 0.001, f64::MAX).unwrap();
+    assert!(!exit.front_face());
+    assert!((exit.point().z + 1.2).abs() < 1e-4);
+    assert!((exit.normal() - Vec3::new(0.0, 0.0, 1.0)).len() < 1e-3);
+}
+
+#[test]
+fn twisted_and_repeated_distances() {
+    let twisted = distances::Cuboid::new(Vec3::new(0.3, 1.0, 0.3)).twist(90.0);
+    let sdf = Sdf::new(twisted, bounds(1.2), Lambertian::new(RGB::default())).step(0.5).max_steps(1000);
+
+    let mut hits = 0;
+    for _ in 0..500 {
+        let origin = Vec3::random_unit_vector() * 3.0;
+        let ray = Ray::new(origin, Vec3::<f64>::random_in_unit_sphere() * 0.5 - origin);
+        if let Some(hit_record) = sdf.hit(&ray, 0.001, f64::MAX) {
+            hits += 1;
+            assert!(twisted.distance(hit_record.point()).abs() < 1e-3);
+        }
+    }
+    assert!(hits > 100);
+
+    // Ray along a row of repeated spheres stops at the first sphere in bounds.
+    let row = Sdf::new(distances::Sphere::new(0.25).repeat(Vec3::new(1.0, 0.0, 0.0)), AABB::from_two_points(Vec3::new(1.5, -1.0, -1.0), Vec3::new(10.0, 1.0, 1.0)), Lambertian::new(RGB::default()));
+    let hit_record = row.hit(&Ray::new(Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0)), 0.001, f64::MAX).unwrap();
+    assert!((hit_record.point().x - 1.75).abs() < 1e-4);
+}
+
+#[test]
+fn sdf_shapes() {
+    let blob = distances::Cuboid::new(Vec3::new(0.25, 0.25, 0.25)).round(0.05)
+        .smooth_union(distances::Sphere::new(0.22).translate(Vec3::new(0.0, 0.3, 0.0)), 0.15);
+    let twisted = distances::Cuboid::new(Vec3::new(0.15, 0.45, 0.15)).round(0.02).twist(120.0);
+    let pebbles = distances::Sphere::new(0.08).repeat(Vec3::new(0.3, 0.0, 0.3));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sdf::new(blob.translate(Vec3::new(-0.8, -0.2, -1.8)), AABB::from_two_points(Vec3::new(-1.2, -0.6, -2.2), Vec3::new(-0.4, 0.4, -1.4)), Lambertian::new(RGB(0.8, 0.4, 0.2))));
+    scene.add_object(Sdf::new(twisted.translate(Vec3::new(0.0, -0.05, -1.8)), AABB::from_two_points(Vec3::new(-0.4, -0.6, -2.2), Vec3::new(0.4, 0.5, -1.4)), Dielectric::new(RGB(0.9, 0.95, 1.0), 1.5)).step(0.5));
+    scene.add_object(Sdf::new(pebbles.translate(Vec3::new(0.0, -0.5, 0.0)), AABB::from_two_points(Vec3::new(0.4, -0.6, -2.6), Vec3::new(1.6, -0.4, -1.2)), Metal::new(RGB(0.7, 0.7, 0.8), 0.1)));
+    scene.add_object(Plane::new(Vec3::new(0.0, -0.5, 0.0), Vec3::new(0.0, 1.0, 0.0), Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/sdf.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -1,5 +1,6 @@
 mod configuration;
 use configuration::*;
+use rayimg::backgrounds::Sky;
 
 #[test]
 fn create_sky_image() {
@@ -14,3 +15,22 @@ fn create_sky_image() {
     let output_file = std::fs::File::create("tests/output/sky.ppm").expect("Failed to create test file");
     renderer.render_multithreaded(&mut P3ImageWriter::new(BOUNDS, output_file));
 }
+
+#[test]
+fn create_physical_sky_image() {
+    let sky = Sky::new(Vec3::new(-1.0, 0.6, -0.5)).turbidity(2.5).ground_albedo(RGB(0.4, 0.35, 0.3));
+    assert!(sky.sun().r() > sky.sun().b());
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -1.0), 0.5, Lambertian::new(RGB(0.8, 0.8, 0.8))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.4, 0.35, 0.3))));
+
+    let camera = Camera::new().position(Vec3::new(0.0, 0.3, 0.5)).target(Vec3::new(0.0, 0.1, -1.0)).build();
+    let renderer = Renderer::new(scene, camera)
+        .background(sky)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/physical_sky.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
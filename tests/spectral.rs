@@ -0,0 +1,69 @@
+mod configuration;
+use configuration::*;
+use rayimg::ImageWrite;
+
+struct Buffer {
+    bounds: (usize, usize),
+    pixels: Vec<RGB>
+}
+
+impl ImageWrite for Buffer {
+    fn write_all(&mut self, colors: &[RGB]) {
+        self.pixels.extend_from_slice(colors);
+    }
+
+    fn bounds(&self) -> (usize, usize) {
+        self.bounds
+    }
+}
+
+#[test]
+fn spectral_mode_preserves_colors() {
+    for color in [RGB(0.2, 0.5, 0.8), RGB(0.9, 0.6, 0.1), RGB(0.5, 0.5, 0.5)] {
+        let mut scene = Scene::new();
+        scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, 10.0), 0.5, Lambertian::new(RGB(1.0, 1.0, 1.0))));
+
+        let renderer = Renderer::new(scene, Camera::default())
+            .ray_miss(move |_| color)
+            .spectral(true)
+            .sample_count(500)
+            .build();
+
+        let mut buffer = Buffer { bounds: (8, 8), pixels: Vec::new() };
+        renderer.render(&mut buffer);
+
+        // Output is gamma corrected with gamma 2.0.
+        let average = buffer.pixels.iter().fold(RGB::default(), |sum, &pixel| sum + pixel * pixel) / buffer.pixels.len() as f64;
+        for (channel, expected) in [(average.r(), color.r()), (average.g(), color.g()), (average.b(), color.b())] {
+            assert!((channel - expected).abs() < 0.02, "{:?} rendered as {:?}", color, average);
+        }
+    }
+}
+
+#[test]
+fn spectral_prism() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-0.6, 0.0, -1.5), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).dispersion(Dispersion::DIAMOND)));
+    scene.add_object(Sphere::new(Vec3::new(0.6, 0.0, -1.5), 0.5, Lambertian::new(RGB(0.1, 0.8, 0.2))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .spectral(true)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/spectral.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,86 @@
+mod configuration;
+use configuration::*;
+use rayimg::Hit;
+
+/// Follows rays entering `sphere` from above until they leave it, returning weights of leaving rays.
+fn escaped_weights(sphere: &Sphere, samples: usize) -> Vec<RGB> {
+    let mut escaped = Vec::with_capacity(samples);
+    for _ in 0..samples {
+        let mut ray = Ray::new(Vec3::new(0.0, 5.0, 0.0), Vec3::new(0.0, -1.0, 0.0));
+        let mut weight = RGB(1.0, 1.0, 1.0);
+
+        for _ in 0..10_000 {
+            let Some(hit_record) = sphere.hit(&ray, 0.001, f64::MAX) else {
+                escaped.push(weight);
+                break;
+            };
+
+            let (scattered, color) = hit_record.scatter(&ray).expect("Subsurface material must not absorb rays");
+            (ray, weight) = (scattered, weight * color);
+        }
+    }
+
+    escaped
+}
+
+/// Returns mean of `weights` and standard error of the mean.
+fn mean_and_error(weights: &[RGB]) -> (RGB, RGB) {
+    let count = weights.len() as f64;
+    let mean = weights.iter().fold(RGB::default(), |sum, &weight| sum + weight) / count;
+    let variance = weights.iter().fold(RGB::default(), |sum, &weight| sum + (weight - mean) * (weight - mean)) / (count - 1.0);
+    (mean, RGB(variance.0.sqrt(), variance.1.sqrt(), variance.2.sqrt()) / count.sqrt())
+}
+
+#[test]
+fn white_subsurface_conserves_energy() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Subsurface::new(RGB(1.0, 1.0, 1.0), RGB(0.2, 0.2, 0.2)));
+    let escaped = escaped_weights(&sphere, 2_000);
+    assert_eq!(escaped.len(), 2_000);
+    for weight in escaped {
+        assert!((weight.r() - 1.0).abs() < 1e-9 && (weight.g() - 1.0).abs() < 1e-9 && (weight.b() - 1.0).abs() < 1e-9, "escaped {:?}", weight);
+    }
+}
+
+#[test]
+fn chromatic_subsurface_conserves_energy() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Subsurface::new(RGB(1.0, 1.0, 1.0), RGB(0.3, 0.2, 0.1)));
+    let (mean, error) = mean_and_error(&escaped_weights(&sphere, 20_000));
+    assert!((mean.r() - 1.0).abs() < 5.0 * error.r() && (mean.g() - 1.0).abs() < 5.0 * error.g() && (mean.b() - 1.0).abs() < 5.0 * error.b(), "escaped {:?} ± {:?}", mean, error);
+}
+
+#[test]
+fn subsurface_albedo_sets_color() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Subsurface::new(RGB(0.8, 0.5, 0.2), RGB(0.05, 0.05, 0.05)).ior(1.0));
+    let (escaped, _) = mean_and_error(&escaped_weights(&sphere, 5_000));
+    assert!(escaped.r() > escaped.g() && escaped.g() > escaped.b(), "escaped {:?}", escaped);
+    assert!((escaped.g() - 0.5).abs() < 0.1, "escaped {:?}", escaped);
+}
+
+#[test]
+fn subsurface_materials() {
+    let light = Sphere::new(Vec3::new(-1.0, 3.0, 0.0), 1.5, DiffuseLight::new(RGB(3.0, 3.0, 3.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.1, 0.0, -2.0), 0.5, Subsurface::new(RGB(0.9, 0.85, 0.7), RGB(0.2, 0.2, 0.2)).ior(1.44)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -2.0), 0.5, Subsurface::new(RGB(0.95, 0.6, 0.5), RGB(0.4, 0.1, 0.05))));
+    scene.add_object(Sphere::new(Vec3::new(1.1, 0.0, -2.0), 0.5, Lambertian::new(RGB(0.95, 0.6, 0.5))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            ((Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t) * 0.3).into()
+        })
+        .lights(lights)
+        .ray_depth(200)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/subsurface.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
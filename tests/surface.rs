@@ -0,0 +1,81 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::*, Hit, Scatter};
+
+#[test]
+fn sphere_tangents_follow_uv() {
+    let sphere = Sphere::new(Vec3::new(1.0, 2.0, -3.0), 2.0, Lambertian::new(RGB(0.5, 0.5, 0.5)));
+    let delta = 1e-6;
+
+    for direction in [Vec3::new(1.0, 0.3, -0.2), Vec3::new(-0.4, -0.7, 0.5), Vec3::new(0.1, 0.2, 1.0)] {
+        let ray = Ray::new(Vec3::new(1.0, 2.0, -3.0) + direction * 10.0, -direction);
+        let hit_record = sphere.hit(&ray, 0.001, f64::MAX).expect("Ray must hit the sphere");
+        let (dpdu, dpdv) = hit_record.tangents();
+        let (u, v) = hit_record.uv();
+
+        assert!(dpdu.dot(&hit_record.normal()).abs() < 1e-9);
+        assert!(dpdv.dot(&hit_record.normal()).abs() < 1e-9);
+
+        // Moving along the tangents by small step must change uv by the same step.
+        for (tangent, expected) in [(dpdu, (u + delta, v)), (dpdv, (u, v + delta))] {
+            let point = hit_record.point() + tangent * delta;
+            let ray = Ray::new(Vec3::new(1.0, 2.0, -3.0) + (point - Vec3::new(1.0, 2.0, -3.0)) * 10.0, Vec3::new(1.0, 2.0, -3.0) - point);
+            let (u, v) = sphere.hit(&ray, 0.001, f64::MAX).expect("Ray must hit the sphere").uv();
+            assert!((u - expected.0).abs() < 1e-9 && (v - expected.1).abs() < 1e-9);
+        }
+    }
+}
+
+#[test]
+fn triangle_shading_normal() {
+    let triangle = Triangle::new([Vec3::new(0.0, 0.0, 0.0), Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0)], Lambertian::new(RGB(0.5, 0.5, 0.5)))
+        .normals([Vec3::new(0.0, 0.0, 1.0), Vec3::new(1.0, 0.0, 1.0), Vec3::new(0.0, 1.0, 1.0)]);
+
+    let front = triangle.hit(&Ray::new(Vec3::new(0.5, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0)), 0.001, f64::MAX).unwrap();
+    assert_eq!(front.geometric_normal(), Vec3::new(0.0, 0.0, 1.0));
+    assert!(front.normal().x > 0.0 && front.normal().z > 0.0);
+    assert!((front.normal().len() - 1.0).abs() < 1e-9);
+
+    let back = triangle.hit(&Ray::new(Vec3::new(0.5, 0.0, -1.0), Vec3::new(0.0, 0.0, 1.0)), 0.001, f64::MAX).unwrap();
+    assert_eq!(back.geometric_normal(), Vec3::new(0.0, 0.0, -1.0));
+    assert!(back.normal().x < 0.0 && back.normal().z < 0.0);
+}
+
+#[test]
+fn bump_map_tilts_normal() {
+    // Height grows along `u`, so the surface faces towards `-u`.
+    let height = ImageTexture::new(2, 1, vec![RGB(0.0, 0.0, 0.0), RGB(1.0, 1.0, 1.0)]).wrap(Wrap::Clamp);
+    let material = BumpMap::new(Lambertian::new(RGB(1.0, 1.0, 1.0)), height).strength(0.5);
+
+    let ray = Ray::new(Vec3::new(0.5, 0.5, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let mut hit_record = HitRecord::new(1.0, Vec3::new(0.5, 0.5, 0.0));
+    hit_record.set_face_normal(&ray, Vec3::new(0.0, 0.0, 1.0));
+    hit_record.set_uv(0.5, 0.5);
+    hit_record.set_tangents(Vec3::new(1.0, 0.0, 0.0), Vec3::new(0.0, 1.0, 0.0));
+
+    let (towards, _) = material.evaluate(&ray, &hit_record, Vec3::new(-1.0, 0.0, 1.0)).unwrap();
+    let (away, _) = material.evaluate(&ray, &hit_record, Vec3::new(1.0, 0.0, 1.0)).unwrap();
+    assert!(towards.r() > away.r());
+}
+
+#[test]
+fn normal_and_bump_mapped_spheres() {
+    let tiles = ImageTexture::new(2, 2, vec![RGB(0.5, 0.5, 1.0), RGB(0.8, 0.5, 0.8), RGB(0.5, 0.8, 0.8), RGB(0.5, 0.5, 1.0)]);
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-0.6, 0.0, -1.5), 0.5, NormalMap::new(Lambertian::new(RGB(0.8, 0.3, 0.3)), tiles)));
+    scene.add_object(Sphere::new(Vec3::new(0.6, 0.0, -1.5), 0.5, BumpMap::new(Metal::new(RGB(0.8, 0.8, 0.8), 0.1), Noise::perlin(8.0)).strength(0.05)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/surface_maps.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,82 @@
+mod configuration;
+use configuration::*;
+use rayimg::{textures::*, ImageWrite, Texture};
+
+#[test]
+fn load_ppm_texture() {
+    let path = temp_path("texture.ppm");
+    let mut writer = P3ImageWriter::new((2, 2), std::fs::File::create(&path).unwrap());
+    writer.write_all(&[RGB(1.0, 0.0, 0.0), RGB(0.0, 1.0, 0.0), RGB(0.0, 0.0, 1.0), RGB(0.5, 0.5, 0.5)]);
+    drop(writer);
+
+    let texture = ImageTexture::load(&path).expect("Failed to load texture").wrap(Wrap::Clamp);
+    assert_eq!(texture.bounds(), (2, 2));
+    assert_eq!(texture.value(0.0, 1.0, Vec3::default()), RGB(1.0, 0.0, 0.0));
+    assert_eq!(texture.value(1.0, 0.0, Vec3::default()).r(), (128.0f64 / 255.0).powi(2));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn load_png_texture() {
+    let path = temp_path("texture.png");
+    let mut encoder = png::Encoder::new(std::fs::File::create(&path).unwrap(), 2, 1);
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header().unwrap().write_image_data(&[255, 0, 0, 255, 0, 0, 255, 128]).unwrap();
+
+    let texture = ImageTexture::load(&path).expect("Failed to load texture");
+    assert_eq!(texture.bounds(), (2, 1));
+    assert_eq!(texture.value(0.25, 0.5, Vec3::default()), RGB(1.0, 0.0, 0.0));
+    assert_eq!(texture.value(0.75, 0.5, Vec3::default()), RGB(0.0, 0.0, 1.0));
+    assert_eq!(texture.clone().wrap(Wrap::Repeat).value(0.0, 0.5, Vec3::default()), RGB(0.5, 0.0, 0.5));
+    assert_eq!(texture.wrap(Wrap::Mirror).value(0.0, 0.5, Vec3::default()), RGB(1.0, 0.0, 0.0));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn load_alpha_texture() {
+    let path = temp_path("alpha.png");
+    let mut encoder = png::Encoder::new(std::fs::File::create(&path).unwrap(), 2, 1);
+    encoder.set_color(png::ColorType::GrayscaleAlpha);
+    encoder.set_depth(png::BitDepth::Eight);
+    encoder.write_header().unwrap().write_image_data(&[255, 0, 0, 255]).unwrap();
+
+    let alpha = ImageTexture::load_alpha(&path).expect("Failed to load texture").wrap(Wrap::Clamp);
+    assert_eq!(alpha.value(0.0, 0.5, Vec3::default()), RGB(0.0, 0.0, 0.0));
+    assert_eq!(alpha.value(1.0, 0.5, Vec3::default()), RGB(1.0, 1.0, 1.0));
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn unsupported_texture() {
+    assert!(ImageTexture::load("tests/configuration.rs").is_err());
+}
+
+#[test]
+fn textured_spheres() {
+    let earth_like = ImageTexture::new(4, 2, vec![
+        RGB(0.1, 0.3, 0.8), RGB(0.2, 0.6, 0.2), RGB(0.1, 0.3, 0.8), RGB(0.6, 0.5, 0.2),
+        RGB(0.2, 0.6, 0.2), RGB(0.1, 0.3, 0.8), RGB(0.6, 0.5, 0.2), RGB(0.1, 0.3, 0.8)
+    ]);
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.1, 0.0, -1.5), 0.5, Lambertian::new(Noise::marble(4.0, 7))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -1.5), 0.5, Lambertian::new(earth_like)));
+    scene.add_object(Sphere::new(Vec3::new(1.1, 0.0, -1.5), 0.5, Metal::new(RGB(0.8, 0.6, 0.2), Noise::turbulence(8.0, 5))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(Checker::new(0.5, RGB(0.2, 0.3, 0.1), RGB(0.9, 0.9, 0.9)))));
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/textures.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
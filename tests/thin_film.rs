@@ -0,0 +1,72 @@
+mod configuration;
+use configuration::*;
+use rayimg::Scatter;
+
+#[test]
+fn film_without_thickness_keeps_fresnel() {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0)).with_wavelength(550.0);
+    let direction = Vec3::new(0.1, 0.0, 1.0);
+
+    let bare = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.3);
+    let coated = Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).roughness(0.3).thin_film(ThinFilm::new(0.0, 1.33));
+
+    let (bare, bare_pdf) = bare.evaluate(&ray, &hit_record(&ray, true), direction).unwrap();
+    let (coated, coated_pdf) = coated.evaluate(&ray, &hit_record(&ray, true), direction).unwrap();
+    assert!((bare.g() - coated.g()).abs() < 1e-9 && (bare_pdf - coated_pdf).abs() < 1e-9);
+}
+
+#[test]
+fn film_color_depends_on_thickness() {
+    let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0));
+    let color = |thickness: f64| {
+        let metal = Metal::new(RGB(0.6, 0.6, 0.6), 0.0).thin_film(ThinFilm::new(thickness, 2.0));
+        metal.scatter(&ray, &hit_record(&ray, true)).unwrap().1
+    };
+
+    let (thin, thick) = (color(60.0), color(120.0));
+    assert!(thin.r() > thin.b() && thick.b() > thick.r(), "thin {:?}, thick {:?}", thin, thick);
+    for channel in [thin.r(), thin.g(), thin.b(), thick.r(), thick.g(), thick.b()] {
+        assert!((0.0..=1.0).contains(&channel));
+    }
+}
+
+#[test]
+fn film_reflectance_varies_with_wavelength() {
+    let metal = Metal::new(RGB(0.6, 0.6, 0.6), 0.0).thin_film(ThinFilm::new(300.0, 1.5));
+    let reflectance = |wavelength: f64| {
+        let ray = Ray::new(Vec3::new(0.0, 0.0, 1.0), Vec3::new(0.0, 0.0, -1.0)).with_wavelength(wavelength);
+        metal.scatter(&ray, &hit_record(&ray, true)).unwrap().1.g()
+    };
+
+    // Waves reflected by both sides of the film are in phase for 450 nm and in antiphase for 600 nm.
+    assert!(metal.is_dispersive());
+    assert!(reflectance(450.0) > reflectance(600.0) + 0.2);
+}
+
+#[test]
+fn iridescent_materials() {
+    let light = Sphere::new(Vec3::new(0.0, 3.0, -1.0), 1.0, DiffuseLight::new(RGB(6.0, 6.0, 6.0)));
+
+    let mut scene = Scene::new();
+    scene.add_object(Sphere::new(Vec3::new(-1.1, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.0).thin_film(ThinFilm::new(400.0, 1.33))));
+    scene.add_object(Sphere::new(Vec3::new(0.0, 0.0, -2.0), 0.5, Metal::new(RGB(0.6, 0.6, 0.6), 0.05).thin_film(ThinFilm::new(250.0, 2.2))));
+    scene.add_object(Sphere::new(Vec3::new(1.1, 0.0, -2.0), 0.5, Dielectric::new(RGB(1.0, 1.0, 1.0), 1.5).thin_film(ThinFilm::new(600.0, 1.4)).roughness(0.1)));
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+    scene.add_object(light.clone());
+
+    let mut lights = Scene::new();
+    lights.add_object(light);
+
+    let renderer = Renderer::new(scene, Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .lights(lights)
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/thin_film.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}
@@ -0,0 +1,103 @@
+mod configuration;
+use configuration::*;
+use rayimg::{BVHNode, Hit};
+use std::sync::Arc;
+
+fn close(a: Vec3<f64>, b: Vec3<f64>) -> bool {
+    (a - b).len() < 1e-9
+}
+
+#[test]
+fn transform_composes_in_call_order() {
+    let transform = Transform::new().scale(Vec3::new(2.0, 2.0, 2.0)).rotate(Vec3::new(0.0, 0.0, 1.0), 90.0).translate(Vec3::new(1.0, 0.0, 0.0));
+
+    assert!(close(transform.point(Vec3::new(1.0, 0.0, 0.0)), Vec3::new(1.0, 2.0, 0.0)));
+    assert!(close(transform.vector(Vec3::new(1.0, 0.0, 0.0)), Vec3::new(0.0, 2.0, 0.0)));
+    assert!(close(transform.inverse().point(Vec3::new(1.0, 2.0, 0.0)), Vec3::new(1.0, 0.0, 0.0)));
+    assert!(Transform::from_matrix(Mat4::scaling(Vec3::new(1.0, 0.0, 1.0))).is_none());
+}
+
+#[test]
+fn rotated_sphere_hit() {
+    let sphere = Sphere::new(Vec3::new(1.0, 0.0, 0.0), 0.5, Lambertian::new(RGB::default()));
+    let rotated = Transformed::new(sphere, Transform::new().rotate(Vec3::new(0.0, 1.0, 0.0), 90.0));
+
+    // Rotation by 90 degrees around y moves +x to -z.
+    let hit_record = rotated.hit(&Ray::new(Vec3::new(0.0, 0.0, 5.0), Vec3::new(0.0, 0.0, -2.0)), 0.001, f64::MAX).unwrap();
+    assert!(close(hit_record.point(), Vec3::new(0.0, 0.0, -0.5)));
+    assert!((hit_record.t() - 2.75).abs() < 1e-9);
+    assert!(close(hit_record.normal(), Vec3::new(0.0, 0.0, 1.0)));
+    assert!(hit_record.front_face());
+    assert!(hit_record.material().is_some());
+}
+
+#[test]
+fn non_uniform_scale_normals() {
+    let ellipsoid = Transformed::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default())), Transform::new().scale(Vec3::new(2.0, 1.0, 1.0)));
+
+    // Point (sqrt(2), sqrt(2) / 2, 0) lies on the ellipsoid x^2 / 4 + y^2 = 1, its normal is proportional to (x / 4, y, 0).
+    let point = Vec3::new(2.0f64.sqrt(), 2.0f64.sqrt() / 2.0, 0.0);
+    let hit_record = ellipsoid.hit(&Ray::new(point * 2.0, -point), 0.001, f64::MAX).unwrap();
+    assert!(close(hit_record.point(), point));
+    assert!(close(hit_record.normal(), Vec3::new(point.x / 4.0, point.y, 0.0).normalize()));
+
+    // From inside the normal faces the ray.
+    let hit_record = ellipsoid.hit(&Ray::new(Vec3::new(0.0, 0.0, 0.0), point), 0.001, f64::MAX).unwrap();
+    assert!(!hit_record.front_face());
+    assert!(close(hit_record.normal(), -Vec3::new(point.x / 4.0, point.y, 0.0).normalize()));
+}
+
+#[test]
+fn transformed_bounding_box() {
+    let sphere = Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB::default()));
+    let transformed = Transformed::new(sphere, Transform::new().scale(Vec3::new(1.0, 2.0, 3.0)).translate(Vec3::new(5.0, 0.0, 0.0)));
+
+    let aabb = transformed.bounding();
+    for (axis, (min, max)) in aabb.axes.iter().zip([(4.0, 6.0), (-2.0, 2.0), (-3.0, 3.0)]) {
+        assert!((axis.min - min).abs() < 1e-9 && (axis.max - max).abs() < 1e-9);
+    }
+}
+
+#[test]
+fn transformed_light_sampling() {
+    let light = Transformed::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, DiffuseLight::new(RGB(1.0, 1.0, 1.0))), Transform::new().scale(Vec3::new(0.5, 0.5, 0.5)).translate(Vec3::new(0.0, 0.0, -4.0)));
+    let origin = Vec3::new(0.0, 0.0, 0.0);
+
+    // Solid angle of sphere with radius 0.5 at distance 4.
+    let cos_max = (1.0 - 0.25f64 / 16.0).sqrt();
+    let expected = 1.0 / (2.0 * std::f64::consts::PI * (1.0 - cos_max));
+    assert!((light.pdf_value(origin, Vec3::new(0.0, 0.0, -1.0)) - expected).abs() / expected < 1e-6);
+
+    for _ in 0..100 {
+        let direction = light.random_direction(origin);
+        assert!(light.hit(&Ray::new(origin, direction), 0.001, f64::MAX).is_some());
+    }
+}
+
+#[test]
+fn instanced_spheres() {
+    let sphere = Arc::new(Sphere::new(Vec3::new(0.0, 0.0, 0.0), 1.0, Lambertian::new(RGB(0.7, 0.3, 0.2))));
+
+    let mut scene = Scene::new();
+    for i in 0..5 {
+        let angle = i as f64 * 36.0;
+        let transform = Transform::new()
+            .scale(Vec3::new(0.15, 0.35, 0.15))
+            .rotate(Vec3::new(0.0, 0.0, 1.0), angle - 72.0)
+            .translate(Vec3::new(i as f64 * 0.5 - 1.0, 0.0, -1.5));
+        scene.add_object(Transformed::new(sphere.clone(), transform));
+    }
+    scene.add_object(Sphere::new(Vec3::new(0.0, -100.5, -1.0), 100.0, Lambertian::new(RGB(0.5, 0.5, 0.5))));
+
+    let renderer = Renderer::new(BVHNode::from_scene(scene), Camera::default())
+        .ray_miss(|r| {
+            let unit_direction = r.direction().normalize();
+            let t = 0.5 * (unit_direction.y + 1.0);
+            (Vec3::new(1.0, 1.0, 1.0) * (1.0 - t) + Vec3::new(0.5, 0.7, 1.0) * t).into()
+        })
+        .sample_count(20)
+        .build();
+
+    let output_file = std::fs::File::create("tests/output/transform.ppm").expect("Failed to create test file");
+    renderer.render_multithreaded(P3ImageWriter::new(BOUNDS, output_file));
+}